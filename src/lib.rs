@@ -0,0 +1,7639 @@
+#![allow(dead_code, non_camel_case_types, non_snake_case,
+         non_upper_case_globals, unused_assignments, unused_mut,
+         unused_variables)]
+#![allow(clippy::missing_safety_doc, clippy::needless_return,
+         clippy::zero_ptr, clippy::redundant_field_names,
+         clippy::ptr_offset_with_cast)]
+/* The loops below test state that the calls in their body change through
+   raw pointers, which clippy cannot see */
+#![allow(clippy::while_immutable_condition)]
+pub(crate) type int32_t = i32;
+pub(crate) type int64_t = i64;
+pub(crate) type uint8_t = u8;
+pub(crate) type uint16_t = u16;
+pub(crate) type uint32_t = u32;
+pub(crate) type uint64_t = u64;
+pub(crate) type size_t = usize;
+pub(crate) type __ssize_t = libc::c_long;
+pub(crate) type ssize_t = __ssize_t;
+pub(crate) type __off_t = libc::c_long;
+pub(crate) type off_t = __off_t;
+pub(crate) type char = libc::c_char;
+pub(crate) type int = libc::c_int;
+pub(crate) type void = libc::c_void;
+extern "C" {
+    fn calloc(_: libc::c_ulong, _: libc::c_ulong) -> *mut libc::c_void;
+    fn free(__ptr: *mut libc::c_void);
+    fn atoi(__nptr: *const libc::c_char) -> libc::c_int;
+    fn memcpy(_: *mut libc::c_void, _: *const libc::c_void, _: libc::c_ulong)
+     -> *mut libc::c_void;
+    fn strcpy(_: *mut libc::c_char, _: *const libc::c_char)
+     -> *mut libc::c_char;
+    fn strcmp(_: *const libc::c_char, _: *const libc::c_char) -> libc::c_int;
+    fn strncmp(_: *const libc::c_char, _: *const libc::c_char,
+               _: libc::c_ulong) -> libc::c_int;
+    fn strtok_r(_: *mut libc::c_char, _: *const libc::c_char,
+                _: *mut *mut libc::c_char) -> *mut libc::c_char;
+    fn strlen(_: *const libc::c_char) -> libc::c_ulong;
+    fn __errno_location() -> *mut libc::c_int;
+    fn open(__file: *const libc::c_char, __oflag: libc::c_int, _: ...)
+     -> libc::c_int;
+    fn lseek(__fd: libc::c_int, __offset: __off_t, __whence: libc::c_int)
+     -> __off_t;
+    fn close(__fd: libc::c_int) -> libc::c_int;
+    fn read(__fd: libc::c_int, __buf: *mut libc::c_void, __nbytes: size_t)
+     -> ssize_t;
+    fn write(__fd: libc::c_int, __buf: *const libc::c_void, __n: size_t)
+     -> ssize_t;
+    fn pread(__fd: libc::c_int, __buf: *mut libc::c_void, __nbytes: size_t,
+             __offset: __off_t) -> ssize_t;
+    fn pwrite(__fd: libc::c_int, __buf: *const libc::c_void, __n: size_t,
+              __offset: __off_t) -> ssize_t;
+    fn mkstemp(__template: *mut libc::c_char) -> libc::c_int;
+    fn memset(_: *mut libc::c_void, _: libc::c_int, _: libc::c_ulong)
+     -> *mut libc::c_void;
+    fn rename(__old: *const libc::c_char, __new: *const libc::c_char)
+     -> libc::c_int;
+    fn fsync(__fd: libc::c_int) -> libc::c_int;
+    fn strcat(_: *mut libc::c_char, _: *const libc::c_char)
+     -> *mut libc::c_char;
+    fn unlink(__name: *const libc::c_char) -> libc::c_int;
+    fn strdup(_: *const libc::c_char) -> *mut libc::c_char;
+    fn memcmp(_: *const libc::c_void, _: *const libc::c_void,
+              _: libc::c_ulong) -> libc::c_int;
+    fn ftruncate(__fd: libc::c_int, __length: __off_t) -> libc::c_int;
+    fn fdatasync(__fildes: libc::c_int) -> libc::c_int;
+    fn strrchr(_: *const libc::c_char, _: libc::c_int) -> *mut libc::c_char;
+    fn usleep(__useconds: libc::c_uint) -> libc::c_int;
+    fn access(__name: *const libc::c_char, __type: libc::c_int)
+     -> libc::c_int;
+    fn strtol(__nptr: *const libc::c_char, __endptr: *mut *mut libc::c_char,
+              __base: libc::c_int) -> libc::c_long;
+    fn getpid() -> libc::c_int;
+    fn time(__timer: *mut libc::time_t) -> libc::time_t;
+    fn strchr(_: *const libc::c_char, _: libc::c_int) -> *mut libc::c_char;
+    fn memmove(_: *mut libc::c_void, _: *const libc::c_void, _: libc::c_ulong)
+     -> *mut libc::c_void;
+}
+
+use crate::MetaCommandResult::*;
+use crate::StatementType::*;
+use std::fmt::Write;
+
+pub(crate) struct InputBuffer {
+    pub buffer: *mut char,
+    pub buffer_length: size_t,
+    pub input_length: ssize_t,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ExecuteResult {
+    Success,
+    NoRowsAffected,
+    TransactionActive,
+    NoTransaction,
+    NoSuchSavepoint,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) enum MetaCommandResult {
+    META_COMMAND_SUCCESS,
+    META_COMMAND_UNRECOGNIZED_COMMAND
+}
+  
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PrepareResult {
+    Success,
+    NegativeId,
+    StringTooLong,
+    SyntaxError,
+    UnrecognizedStatement,
+    UnrecognizedPragma,
+    NoSuchTable,
+    ReadOnly,
+    TypeMismatch,
+    NullId,
+    IdTooLarge
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Error {
+    /* A system call failed: what was being done, and errno */
+    Io(&'static str, libc::c_int),
+    /* The disk has no room left for a page, journal or WAL frame */
+    Full,
+    /*
+  A page does not match its checksum or is cut short. A WAL
+  frame header cut short is reported as page 0.
+  */
+    Corrupt(u32),
+    NotADatabase,
+    /* Format version of a file this build cannot read */
+    Version(u32),
+    /* Page size that is not a power of two from 512 to 65536 */
+    PageSize(u32),
+    DuplicateKey,
+    Syntax(PrepareResult),
+    Locked,
+    /* CREATE TABLE for a name the catalog already lists */
+    TableExists,
+    /* A file name, table name or statement with a NUL byte in it */
+    NulByte,
+    /* A meta command that needs the database to itself, run in BEGIN */
+    TransactionActive,
+    /* A line starting with '.' that names no meta command */
+    UnrecognizedCommand,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) enum StatementType { 
+    STATEMENT_INSERT, 
+    STATEMENT_SELECT,
+    STATEMENT_DELETE,
+    STATEMENT_UPDATE,
+    STATEMENT_PRAGMA,
+    STATEMENT_BEGIN,
+    STATEMENT_COMMIT,
+    STATEMENT_ROLLBACK,
+    STATEMENT_SAVEPOINT,
+    STATEMENT_RELEASE,
+    STATEMENT_ROLLBACK_TO,
+    STATEMENT_CREATE_TABLE
+}
+
+const SAVEPOINT_NAME_SIZE: usize = 32;
+const TABLE_NAME_SIZE: usize = 32;
+const COLUMN_NAME_SIZE: usize = 32;
+const TABLE_MAX_COLUMNS: usize = 64;
+/* Longest text or blob a column takes, a record's size always fits 32 bits */
+const VALUE_MAX_SIZE: usize = 16777216;
+
+/*
+ * System Catalog
+ *   A table like any other, one row per table in the database:
+ *   its name, root page and the CREATE TABLE text its schema is
+ *   parsed from. The file header points at the catalog's root.
+ *   Statements may read it but never change it directly.
+ */
+pub(crate) static CATALOG_SQL: &str =
+    "create table bizdb_catalog (id integer, name text(32), root integer, sql text)";
+
+pub(crate) type ColumnType = uint32_t;
+/* Signed 64 bit, the first column is the key and has to be one */
+pub(crate) const COLUMN_TYPE_INTEGER: ColumnType = 0;
+/* text or text(N): a varint length, then up to N bytes */
+pub(crate) const COLUMN_TYPE_TEXT: ColumnType = 1;
+/* 64 bit floating point */
+pub(crate) const COLUMN_TYPE_REAL: ColumnType = 2;
+/* blob or blob(N): a varint length, then up to N bytes */
+pub(crate) const COLUMN_TYPE_BLOB: ColumnType = 3;
+/* One byte, 0 or 1 */
+pub(crate) const COLUMN_TYPE_BOOLEAN: ColumnType = 4;
+
+#[derive(Copy, Clone)]
+pub(crate) struct Column {
+    pub name: [char; COLUMN_NAME_SIZE + 1],
+    pub type_0: ColumnType,
+    /* Longest text or blob the column takes, 0 for no limit */
+    pub size: uint32_t,
+}
+
+/*
+ * Schema
+ *   Worked out from CREATE TABLE when it runs and again from the
+ *   statement text kept in the catalog whenever it is loaded.
+ *   A row is stored as a record: a null bitmap, one bit per column,
+ *   then each value that is not null in column order. Integers and
+ *   reals take 8 bytes, little-endian whatever the host, booleans 1,
+ *   text and blobs a varint length followed by their bytes.
+ */
+#[derive(Clone)]
+pub(crate) struct Schema {
+    pub name: [char; TABLE_NAME_SIZE + 1],
+    pub columns: Vec<Column>,
+    /* The statement as stored in the catalog */
+    pub sql: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    /* Any column but the key may hold it */
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+    Boolean(bool),
+}
+
+/* One value per column of the schema, in column order */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Row {
+    pub values: Vec<Value>,
+}
+
+/* What a statement run on a Connection did */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Executed {
+    pub result: ExecuteResult,
+    /* Rows a select found, in key order */
+    pub rows: Vec<Row>,
+    /* Lines a pragma reports, as the REPL prints them */
+    pub output: String,
+}
+
+pub(crate) struct Statement {
+    pub type_0: StatementType,
+    pub row_to_insert: Row,
+    /* Table the statement reads or changes */
+    pub table_name: [char; TABLE_NAME_SIZE + 1],
+    pub min_id: uint32_t,
+    pub max_id: uint32_t,
+    /* Columns an update assigns, the values are in row_to_insert */
+    pub set_columns: Vec<bool>,
+    /* Table a create table statement defines */
+    pub schema: Schema,
+    pub pragma: Pragma,
+    /* Only meaningful when pragma_set is true, for the pragma it names */
+    pub pragma_journal_mode: JournalMode,
+    pub pragma_synchronous: Synchronous,
+    pub pragma_busy_timeout: uint32_t,
+    pub pragma_set: bool,
+    pub savepoint_name: [char; SAVEPOINT_NAME_SIZE + 1],
+    /* Rows a select finds */
+    pub rows: Vec<Row>,
+    /* What a pragma reports, a line per setting */
+    pub output: String,
+}
+
+/* Page sizes a database can be created with, powers of two only */
+pub const DEFAULT_PAGE_SIZE: u32 = 4096 as libc::c_int as u32;
+pub(crate) static mut MIN_PAGE_SIZE: uint32_t = 512 as libc::c_int as uint32_t;
+pub(crate) static mut MAX_PAGE_SIZE: uint32_t = 65536 as libc::c_int as uint32_t;
+
+const PAGER_HASH_BUCKETS: usize = 1024;
+/* Default page cache budget, in pages */
+pub(crate) static mut PAGER_DEFAULT_CACHE_SIZE: uint32_t =
+    2000 as libc::c_int as uint32_t;
+
+pub(crate) struct CachedPage {
+    pub page_num: uint32_t,
+    pub data: *mut void,
+    /* Value of Pager.generation when the page was last handed out */
+    pub generation: uint64_t,
+    /* Set when the cached copy differs from the file */
+    pub dirty: bool,
+    pub hash_next: *mut CachedPage,
+    /* LRU list, most recently used first */
+    pub lru_prev: *mut CachedPage,
+    pub lru_next: *mut CachedPage,
+}
+
+pub(crate) struct PageIndexEntry {
+    pub page_num: uint32_t,
+    /* WAL frame or journal record holding the page */
+    pub slot: uint32_t,
+    pub next: *mut PageIndexEntry,
+}
+
+pub(crate) struct PageSnapshot {
+    pub page_num: uint32_t,
+    /* Where the savepoint file holds the page as it was when taken */
+    pub offset: off_t,
+    pub next: *mut PageSnapshot,
+}
+
+pub(crate) struct Savepoint {
+    /* Empty for the level pushed by BEGIN */
+    pub name: [char; SAVEPOINT_NAME_SIZE + 1],
+    /* Pages in the database when the savepoint was taken */
+    pub num_pages: uint32_t,
+    /* Content of each page when the savepoint was taken */
+    pub snapshots: [*mut PageSnapshot; PAGER_HASH_BUCKETS],
+    /* Enclosing savepoint, NULL for the one that began the transaction */
+    pub prev: *mut Savepoint,
+}
+
+pub(crate) struct Pager {
+    pub filename: *mut libc::c_char,
+    pub file_descriptor: int,
+    pub file_length: off_t,
+    /* Recorded in the file header, the layout below follows from it */
+    pub page_size: uint32_t,
+    /* Bytes before the page checksum */
+    pub usable_size: uint32_t,
+    pub leaf_node_space_for_cells: uint32_t,
+    /* Payloads up to this size stay on the leaf, longer ones spill */
+    pub leaf_node_max_local: uint32_t,
+    /* Bytes of a spilled payload kept on the leaf */
+    pub leaf_node_spill_local: uint32_t,
+    /* Leaves using fewer bytes than this borrow from or merge with a sibling */
+    pub leaf_node_min_fill: uint32_t,
+    pub overflow_page_space: uint32_t,
+    pub freelist_trunk_max_leaves: uint32_t,
+    /* Internal nodes with fewer keys than the minimum borrow or merge */
+    pub internal_node_max_cells: uint32_t,
+    pub internal_node_min_cells: uint32_t,
+    pub num_pages: uint32_t,
+    pub cache_size: uint32_t,
+    pub num_cached: uint32_t,
+    pub generation: uint64_t,
+    pub buckets: [*mut CachedPage; PAGER_HASH_BUCKETS],
+    pub lru_head: *mut CachedPage,
+    pub lru_tail: *mut CachedPage,
+    pub pages_read: uint64_t,
+    pub pages_written: uint64_t,
+    /* Set once a page is modified, cleared when the change counter is bumped */
+    pub changed: bool,
+    pub wal_filename: *mut libc::c_char,
+    /* -1 until the WAL file is needed */
+    pub wal_fd: int,
+    pub wal_frames: uint32_t,
+    pub wal_checkpoint_seq: uint32_t,
+    pub wal_salt: [uint32_t; 2],
+    /* Running checksum up to the last frame written */
+    pub wal_checksum: [uint32_t; 2],
+    /* WAL position after the last commit frame */
+    pub wal_committed_frames: uint32_t,
+    pub wal_committed_checksum: [uint32_t; 2],
+    /* Database size in pages recorded by the last commit frame */
+    pub wal_db_size: uint32_t,
+    /* Pages with a copy in the WAL, by page number */
+    pub wal_index: [*mut PageIndexEntry; PAGER_HASH_BUCKETS],
+    pub journal_mode: JournalMode,
+    pub journal_filename: *mut libc::c_char,
+    /* -1 outside of a transaction in rollback mode */
+    pub journal_fd: int,
+    pub journal_records: uint32_t,
+    /* Pages in the database file when the transaction started */
+    pub journal_db_size: uint32_t,
+    /* Cleared when a record is appended, set again by fsync */
+    pub journal_synced: bool,
+    /* Pages whose original content is already in the journal */
+    pub journal_index: [*mut PageIndexEntry; PAGER_HASH_BUCKETS],
+    pub synchronous: Synchronous,
+    /* Writes left until .torn_write cuts one short, 0 when off */
+    pub torn_write_countdown: uint32_t,
+    /* Set once a write was cut short, nothing reaches the files after it */
+    pub crashed: bool,
+    /* Set by BEGIN, statements are not committed one by one */
+    pub in_transaction: bool,
+    /* Innermost savepoint, the bottom one covers the whole transaction */
+    pub savepoints: *mut Savepoint,
+    /* Unlinked file the snapshots are kept in, -1 until one is taken */
+    pub savepoint_fd: int,
+    /* Bytes of it in use, back to 0 when the transaction ends */
+    pub savepoint_file_length: off_t,
+    pub lock: LockLevel,
+    /* Milliseconds to retry a lock held by another connection */
+    pub busy_timeout: uint32_t,
+    /* File header change counter when this connection last looked */
+    pub change_counter: uint32_t,
+    /* Lock byte publishing the snapshot being read, -1 without one */
+    pub read_mark: off_t,
+    /* WAL frames this connection already copied to the database file */
+    pub wal_backfilled: uint32_t,
+}
+pub(crate) struct IntegrityCheck {
+    pub pager: *mut Pager,
+    pub root_page_num: uint32_t,
+    /* Schema the records of the tree being walked must match */
+    pub schema: *mut Schema,
+    /* One entry per page of the database */
+    pub page_use: *mut PageUse,
+    /* Leaves in key order, with the next leaf each one points to */
+    pub leaves: *mut uint32_t,
+    pub next_leaves: *mut uint32_t,
+    pub num_leaves: uint32_t,
+    pub leaf_depth: uint32_t,
+    pub num_problems: uint32_t,
+    /* The report, one problem per line */
+    pub output: String,
+}
+pub(crate) struct Table {
+    pub pager: *mut Pager,
+    pub root_page_num: uint32_t,
+    pub schema: *mut Schema,
+    /* Tables the catalog lists, only filled in on the catalog itself */
+    pub tables: Vec<*mut Table>,
+}
+
+pub(crate) struct Cursor {
+    pub table: *mut Table,
+    pub page_num: uint32_t,
+    pub cell_num: uint32_t,
+    pub end_of_table: bool,
+}
+
+pub(crate) type Pragma = uint32_t;
+pub(crate) const PRAGMA_JOURNAL_MODE: Pragma = 0;
+pub(crate) const PRAGMA_SYNCHRONOUS: Pragma = 1;
+pub(crate) const PRAGMA_BUSY_TIMEOUT: Pragma = 2;
+/* Read only, chosen when the database is created */
+pub(crate) const PRAGMA_PAGE_SIZE: Pragma = 3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum JournalMode {
+    Wal,
+    Rollback
+}
+
+/*
+ * How much a commit is allowed to lose on power loss:
+ * OFF      never sync, the OS decides when data reaches the disk
+ * NORMAL   sync before the database file is overwritten, so it
+ *          stays consistent, but the last commits may be lost
+ * FULL     also sync every commit before reporting success
+ */
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Synchronous {
+    Off,
+    Normal,
+    Full
+}
+
+/* A journal mode or synchronous setting given by a name neither knows */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnknownSetting(pub String);
+
+impl ::std::fmt::Display for UnknownSetting {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Unknown setting \'{}\'.", self.0)
+    }
+}
+
+impl ::std::error::Error for UnknownSetting { }
+
+/*
+ * Lock a connection holds on the database file:
+ * NONE       between statements
+ * SHARED     reading, any number of connections at once
+ * RESERVED   about to write, readers may carry on
+ * EXCLUSIVE  writing the database file, nobody else may read it
+ */
+pub(crate) type LockLevel = uint32_t;
+pub(crate) const LOCK_NONE: LockLevel = 0;
+pub(crate) const LOCK_SHARED: LockLevel = 1;
+pub(crate) const LOCK_RESERVED: LockLevel = 2;
+pub(crate) const LOCK_EXCLUSIVE: LockLevel = 3;
+
+/* What .check found each page to be used for */
+pub(crate) type PageUse = uint8_t;
+pub(crate) const PAGE_USE_NONE: PageUse = 0;
+pub(crate) const PAGE_USE_HEADER: PageUse = 1;
+pub(crate) const PAGE_USE_TREE: PageUse = 2;
+pub(crate) const PAGE_USE_FREELIST: PageUse = 3;
+pub(crate) const PAGE_USE_OVERFLOW: PageUse = 4;
+
+pub(crate) type NodeType = uint32_t;
+pub(crate) const NODE_LEAF: NodeType = 1;
+pub(crate) const NODE_INTERNAL: NodeType = 0;
+
+/*
+ * Common Node Header Layout
+ */
+pub(crate) static mut NODE_TYPE_SIZE: uint32_t =
+    ::std::mem::size_of::<uint8_t>() as uint32_t;
+pub(crate) static mut NODE_TYPE_OFFSET: uint32_t = 0 as libc::c_int as uint32_t;
+pub(crate) static mut IS_ROOT_SIZE: uint32_t =
+    ::std::mem::size_of::<uint8_t>() as uint32_t;
+pub(crate) static mut IS_ROOT_OFFSET: uint32_t = 0;
+/* Unused, keeps the parent pointer and the fields after it aligned */
+pub(crate) static mut NODE_HEADER_PADDING_SIZE: uint32_t =
+    2 as libc::c_int as uint32_t;
+pub(crate) static mut PARENT_POINTER_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as uint32_t;
+pub(crate) static mut PARENT_POINTER_OFFSET: uint32_t = 0;
+pub(crate) static mut COMMON_NODE_HEADER_SIZE: uint8_t = 0;
+
+/*
+ * Internal Node Header Layout
+ */
+
+pub(crate) static mut INTERNAL_NODE_NUM_KEYS_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut INTERNAL_NODE_NUM_KEYS_OFFSET: uint32_t = 0;
+pub(crate) static mut INTERNAL_NODE_RIGHT_CHILD_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut INTERNAL_NODE_RIGHT_CHILD_OFFSET: uint32_t = 0;
+pub(crate) static mut INTERNAL_NODE_HEADER_SIZE: uint32_t = 0;
+/*
+ * Internal Node Body Layout
+ */
+
+pub(crate) static mut INTERNAL_NODE_KEY_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut INTERNAL_NODE_CHILD_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut INTERNAL_NODE_CELL_SIZE: uint32_t = 0;
+/*
+ * Leaf Node Header Layout
+ */
+pub(crate) static mut LEAF_NODE_NUM_CELLS_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_NUM_CELLS_OFFSET: uint32_t = 0;
+pub(crate) static mut LEAF_NODE_NEXT_LEAF_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_NEXT_LEAF_OFFSET: uint32_t = 0;
+/* Start of the cell content, which grows down from the end of the page */
+pub(crate) static mut LEAF_NODE_CONTENT_OFFSET_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_CONTENT_OFFSET_OFFSET: uint32_t = 0;
+pub(crate) static mut LEAF_NODE_HEADER_SIZE: uint32_t = 0;
+/*
+ * Leaf Node Body Layout
+ *   after the header an array of cell pointers, the page offset of
+ *   each cell in key order, then free space, then the cells packed
+ *   together at the end of the page.
+ *   cell: key, payload size, first overflow page (only when the
+ *         payload spills), the payload bytes kept on the leaf,
+ *         padded to a multiple of 4 bytes
+ */
+pub(crate) static mut LEAF_NODE_CELL_POINTER_SIZE: uint32_t =
+    ::std::mem::size_of::<uint16_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_KEY_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_KEY_OFFSET: uint32_t = 0 as libc::c_int as uint32_t;
+pub(crate) static mut LEAF_NODE_PAYLOAD_SIZE_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut LEAF_NODE_PAYLOAD_SIZE_OFFSET: uint32_t = 0;
+pub(crate) static mut LEAF_NODE_CELL_HEADER_SIZE: uint32_t = 0;
+pub(crate) static mut LEAF_NODE_OVERFLOW_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+/* A leaf has to take at least this many cells of the largest size */
+pub(crate) static mut LEAF_NODE_MIN_MAX_CELLS: uint32_t =
+    4 as libc::c_int as uint32_t;
+/*
+ * Overflow Page Layout
+ *   the next page of the chain, 0 on the last, then payload bytes
+ */
+pub(crate) static mut OVERFLOW_PAGE_NEXT_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut OVERFLOW_PAGE_NEXT_OFFSET: uint32_t =
+    0 as libc::c_int as uint32_t;
+pub(crate) static mut OVERFLOW_PAGE_HEADER_SIZE: uint32_t = 0;
+/*
+ * File Header Layout (page 0)
+ */
+pub(crate) static FILE_HEADER_MAGIC: [u8; 16] = *b"bizdb database\x00\x00";
+/*
+  Bump whenever the on-disk layout changes. Files are not upgraded,
+  any other version is refused when the file is opened.
+  1 file header on page 0
+  2 CRC32 trailer on every page
+  3 row layout from CREATE TABLE
+  4 system catalog holding the tables
+  5 typed columns and a null bitmap
+  6 variable-length records and overflow pages
+  */
+pub(crate) static mut FILE_FORMAT_VERSION: uint32_t = 6 as libc::c_int as uint32_t;
+/*
+  Files from before the header: 4096 byte pages, the root node on
+  page 0, or a free-list header there and the root node on page 1.
+  */
+pub(crate) static mut LEGACY_FORMAT_VERSION: uint32_t =
+    0 as libc::c_int as uint32_t;
+pub(crate) static mut LEGACY_PAGE_SIZE: uint32_t =
+    4096 as libc::c_int as uint32_t;
+pub(crate) static mut FILE_HEADER_MAGIC_SIZE: uint32_t =
+    ::std::mem::size_of::<[u8; 16]>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_MAGIC_OFFSET: uint32_t =
+    0 as libc::c_int as uint32_t;
+pub(crate) static mut FILE_HEADER_VERSION_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_VERSION_OFFSET: uint32_t = 0;
+pub(crate) static mut FILE_HEADER_PAGE_SIZE_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_PAGE_SIZE_OFFSET: uint32_t = 0;
+/* Root page of the system catalog */
+pub(crate) static mut FILE_HEADER_ROOT_PAGE_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_ROOT_PAGE_OFFSET: uint32_t = 0;
+pub(crate) static mut FILE_HEADER_FREELIST_TRUNK_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_FREELIST_TRUNK_OFFSET: uint32_t = 0;
+pub(crate) static mut FILE_HEADER_FREELIST_COUNT_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_FREELIST_COUNT_OFFSET: uint32_t = 0;
+pub(crate) static mut FILE_HEADER_CHANGE_COUNTER_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FILE_HEADER_CHANGE_COUNTER_OFFSET: uint32_t = 0;
+pub(crate) static mut FILE_HEADER_SIZE: uint32_t = 0;
+/*
+ * WAL File Layout
+ *   header: magic, format version, page size, checkpoint sequence,
+ *           salt 1, salt 2, checksum 1, checksum 2
+ *   frame:  page number, database size in pages (commit frames only),
+ *           salt 1, salt 2, checksum 1, checksum 2, page data
+ */
+pub(crate) static mut WAL_MAGIC: uint32_t = 0x62697a77 as libc::c_int as uint32_t;
+pub(crate) static mut WAL_FORMAT_VERSION: uint32_t = 1 as libc::c_int as uint32_t;
+pub(crate) static mut WAL_HEADER_SIZE: uint32_t =
+    ::std::mem::size_of::<[uint32_t; 8]>() as libc::c_ulong as uint32_t;
+pub(crate) static mut WAL_FRAME_HEADER_SIZE: uint32_t =
+    ::std::mem::size_of::<[uint32_t; 6]>() as libc::c_ulong as uint32_t;
+/* Checkpoint once a commit leaves this many frames in the WAL */
+pub(crate) static mut WAL_AUTOCHECKPOINT: uint32_t = 1000 as libc::c_int as uint32_t;
+/*
+ * Rollback Journal Layout
+ *   header: magic, format version, page size, database size in pages,
+ *           checksum 1, checksum 2
+ *   record: page number, checksum 1, checksum 2, original page data
+ */
+pub(crate) static mut JOURNAL_MAGIC: uint32_t = 0x62697a6a as libc::c_int as uint32_t;
+pub(crate) static mut JOURNAL_FORMAT_VERSION: uint32_t = 1 as libc::c_int as uint32_t;
+pub(crate) static mut JOURNAL_HEADER_SIZE: uint32_t =
+    ::std::mem::size_of::<[uint32_t; 6]>() as libc::c_ulong as uint32_t;
+pub(crate) static mut JOURNAL_RECORD_HEADER_SIZE: uint32_t =
+    ::std::mem::size_of::<[uint32_t; 3]>() as libc::c_ulong as uint32_t;
+/*
+ * Lock Bytes
+ *   fcntl locks on single bytes 1 GiB into the database file. The
+ *   locks are advisory, so pages stored there are not affected.
+ *   They are open file description locks, owned by the pager's
+ *   descriptor rather than the process, so pagers in one process
+ *   keep each other out just like pagers in different ones.
+ *   connection: read-locked by every connection while it is open
+ *   pending:    held by a writer waiting for readers to finish
+ *   reserved:   write-locked by the one connection that may write
+ *   shared:     read-locked by readers, write-locked when exclusive
+ *   checkpoint: write-locked while a checkpoint copies frames,
+ *               read-locked while a reader takes its snapshot
+ *   read marks: a reader read-locks the byte READ_MARK + n while it
+ *               reads the first n frames of the WAL as its snapshot
+ */
+pub(crate) static mut LOCK_BYTE_CONNECTION: off_t =
+    0x40000000 as libc::c_int as off_t;
+pub(crate) static mut LOCK_BYTE_PENDING: off_t = 0x40000001 as libc::c_int as off_t;
+pub(crate) static mut LOCK_BYTE_RESERVED: off_t = 0x40000002 as libc::c_int as off_t;
+pub(crate) static mut LOCK_BYTE_SHARED: off_t = 0x40000003 as libc::c_int as off_t;
+pub(crate) static mut LOCK_BYTE_CHECKPOINT: off_t =
+    0x40000004 as libc::c_int as off_t;
+pub(crate) static mut LOCK_BYTE_READ_MARK: off_t = 0x40000100 as libc::c_int as off_t;
+/* How long to wait for a lock another connection holds, in milliseconds */
+pub(crate) static mut PAGER_DEFAULT_BUSY_TIMEOUT: uint32_t =
+    5000 as libc::c_int as uint32_t;
+/*
+ * Freelist Trunk Page Layout
+ */
+pub(crate) static mut FREELIST_TRUNK_NEXT_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FREELIST_TRUNK_NEXT_OFFSET: uint32_t =
+    0 as libc::c_int as uint32_t;
+pub(crate) static mut FREELIST_TRUNK_NUM_LEAVES_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut FREELIST_TRUNK_NUM_LEAVES_OFFSET: uint32_t = 0;
+pub(crate) static mut FREELIST_TRUNK_HEADER_SIZE: uint32_t = 0;
+pub(crate) static mut FREELIST_TRUNK_LEAF_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+/*
+ * Page Trailer Layout
+ *   the last bytes of every page hold a CRC32 of the rest of it,
+ *   node and freelist layouts only use the bytes before it
+ */
+pub(crate) static mut PAGE_CHECKSUM_SIZE: uint32_t =
+    ::std::mem::size_of::<uint32_t>() as libc::c_ulong as uint32_t;
+pub(crate) static mut CRC32_TABLE: [uint32_t; 256] = [0; 256];
+
+
+pub(crate) unsafe fn get_node_type(mut node: *mut libc::c_void) -> NodeType {
+    let mut value: uint8_t = *(node.offset(NODE_TYPE_OFFSET as isize) as *mut uint8_t);
+    return value as NodeType;
+}
+
+pub(crate) unsafe fn set_node_type(mut node: *mut libc::c_void, mut type_0: NodeType) {
+    *(node.offset(NODE_TYPE_OFFSET as isize) as *mut uint8_t) = type_0 as uint8_t;
+}
+
+pub(crate) unsafe fn is_node_root(mut node: *mut libc::c_void) -> bool {
+    let mut value: uint8_t =
+        *(node.offset(IS_ROOT_OFFSET as isize) as *mut uint8_t);
+    return value != 0;
+}
+
+pub(crate) unsafe fn set_node_root(mut node: *mut libc::c_void,
+                                       mut is_root: bool) {
+    let mut value: uint8_t = is_root as uint8_t;
+    *(node.offset(IS_ROOT_OFFSET as isize) as *mut uint8_t) = value;
+}
+
+pub(crate) unsafe fn node_parent(mut node: *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(PARENT_POINTER_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn internal_node_num_keys(mut node: *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(INTERNAL_NODE_NUM_KEYS_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn internal_node_right_child(mut node:
+                                                       *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(INTERNAL_NODE_RIGHT_CHILD_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn internal_node_cell(mut node: *mut libc::c_void,
+                                            mut cell_num: uint32_t)
+ -> *mut uint32_t {
+    return node.offset(INTERNAL_NODE_HEADER_SIZE as
+                           isize).offset(cell_num.wrapping_mul(INTERNAL_NODE_CELL_SIZE)
+                                             as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn internal_node_child(mut node: *mut libc::c_void,
+                                             mut child_num: uint32_t)
+ -> *mut uint32_t {
+    /* num_keys was checked against the page by get_node */
+    let mut num_keys: uint32_t = *internal_node_num_keys(node);
+    debug_assert!(child_num <= num_keys);
+    if child_num == num_keys {
+        return internal_node_right_child(node)
+    } else { return internal_node_cell(node, child_num) };
+}
+
+pub(crate) unsafe fn internal_node_key(mut node: *mut libc::c_void,
+                                           mut key_num: uint32_t)
+ -> *mut uint32_t {
+    return (internal_node_cell(node, key_num) as
+                *mut libc::c_void).offset(INTERNAL_NODE_CHILD_SIZE as isize)
+               as *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_num_cells(mut node: *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(LEAF_NODE_NUM_CELLS_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_next_leaf(mut node: *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(LEAF_NODE_NEXT_LEAF_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_content_offset(mut node: *mut libc::c_void)
+ -> *mut uint32_t {
+    return node.offset(LEAF_NODE_CONTENT_OFFSET_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_cell_pointer(mut node: *mut libc::c_void,
+                                     mut cell_num: uint32_t)
+ -> *mut uint16_t {
+    return node.offset(LEAF_NODE_HEADER_SIZE as
+                           isize).offset(cell_num.wrapping_mul(LEAF_NODE_CELL_POINTER_SIZE)
+                                             as isize) as *mut uint16_t;
+}
+
+pub(crate) unsafe fn leaf_node_cell(mut node: *mut libc::c_void,
+                                        mut cell_num: uint32_t)
+ -> *mut libc::c_void {
+    return node.offset(*leaf_node_cell_pointer(node, cell_num) as isize);
+}
+
+pub(crate) unsafe fn leaf_node_key(mut node: *mut libc::c_void,
+                                       mut cell_num: uint32_t)
+ -> *mut uint32_t {
+    return leaf_node_cell(node, cell_num).offset(LEAF_NODE_KEY_OFFSET as
+                                                     isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_payload_size(mut node: *mut libc::c_void,
+                                     mut cell_num: uint32_t)
+ -> *mut uint32_t {
+    return leaf_node_cell(node,
+                          cell_num).offset(LEAF_NODE_PAYLOAD_SIZE_OFFSET as
+                                               isize) as *mut uint32_t;
+}
+
+/* Only meaningful when the payload spills */
+pub(crate) unsafe fn leaf_node_overflow(mut node: *mut libc::c_void,
+                                 mut cell_num: uint32_t) -> *mut uint32_t {
+    return leaf_node_cell(node,
+                          cell_num).offset(LEAF_NODE_CELL_HEADER_SIZE as
+                                               isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_payload(mut pager: *mut Pager,
+                                mut node: *mut libc::c_void,
+                                mut cell_num: uint32_t)
+ -> *mut libc::c_void {
+    let mut offset: uint32_t = LEAF_NODE_CELL_HEADER_SIZE;
+    if payload_spills(pager, *leaf_node_payload_size(node, cell_num)) {
+        offset = offset.wrapping_add(LEAF_NODE_OVERFLOW_SIZE)
+    }
+    return leaf_node_cell(node, cell_num).offset(offset as isize);
+}
+
+pub(crate) unsafe fn leaf_node_cell_size(mut pager: *mut Pager,
+                                  mut node: *mut libc::c_void,
+                                  mut cell_num: uint32_t) -> uint32_t {
+    return cell_size(pager, *leaf_node_payload_size(node, cell_num));
+}
+
+/* Bytes the cells take, their pointers included */
+pub(crate) unsafe fn leaf_node_used_space(mut pager: *mut Pager,
+                                   mut node: *mut libc::c_void) -> uint32_t {
+    return (*pager).usable_size.wrapping_sub(*leaf_node_content_offset(node)).wrapping_add((*leaf_node_num_cells(node)).wrapping_mul(LEAF_NODE_CELL_POINTER_SIZE));
+}
+
+pub(crate) unsafe fn leaf_node_free_space(mut pager: *mut Pager,
+                                   mut node: *mut libc::c_void) -> uint32_t {
+    return (*pager).leaf_node_space_for_cells.wrapping_sub(leaf_node_used_space(pager,
+                                                                                node));
+}
+
+/* Whether a cell's pointer and bytes lie inside the cell content */
+pub(crate) unsafe fn leaf_node_cell_ok(mut pager: *mut Pager,
+                                mut node: *mut libc::c_void,
+                                mut cell_num: uint32_t) -> bool {
+    let mut offset: uint32_t =
+        *leaf_node_cell_pointer(node, cell_num) as uint32_t;
+    /* Cell sizes are rounded up to 4 bytes, so cells stay aligned */
+    if offset & 3 as libc::c_int as libc::c_uint !=
+           0 as libc::c_int as libc::c_uint ||
+           offset < *leaf_node_content_offset(node) ||
+           offset.wrapping_add(LEAF_NODE_CELL_HEADER_SIZE) >
+               (*pager).usable_size {
+        return 0 as libc::c_int != 0
+    }
+    return (offset as uint64_t).wrapping_add(leaf_node_cell_size(pager, node,
+                                                                 cell_num) as
+                                                 uint64_t) <=
+               (*pager).usable_size as uint64_t;
+}
+
+pub(crate) unsafe fn leaf_node_fits(mut pager: *mut Pager,
+                                    mut node: *mut libc::c_void) -> bool {
+    /* The cell pointers end before the cell content starts */
+    return (*leaf_node_num_cells(node) as
+                uint64_t).wrapping_mul(LEAF_NODE_CELL_POINTER_SIZE as
+                                           uint64_t).wrapping_add(LEAF_NODE_HEADER_SIZE
+                                                                      as
+                                                                      uint64_t)
+               <= *leaf_node_content_offset(node) as uint64_t &&
+               *leaf_node_content_offset(node) <= (*pager).usable_size;
+}
+
+pub(crate) unsafe fn payload_spills(mut pager: *mut Pager,
+                             mut payload_size: uint32_t) -> bool {
+    return payload_size > (*pager).leaf_node_max_local;
+}
+
+pub(crate) unsafe fn payload_local_size(mut pager: *mut Pager,
+                                 mut payload_size: uint32_t) -> uint32_t {
+    if payload_spills(pager, payload_size) {
+        return (*pager).leaf_node_spill_local
+    }
+    return payload_size;
+}
+
+pub(crate) unsafe fn cell_size(mut pager: *mut Pager, mut payload_size: uint32_t)
+ -> uint32_t {
+    let mut size: uint32_t =
+        LEAF_NODE_CELL_HEADER_SIZE.wrapping_add(payload_local_size(pager,
+                                                                   payload_size));
+    if payload_spills(pager, payload_size) {
+        size = size.wrapping_add(LEAF_NODE_OVERFLOW_SIZE)
+    }
+    return size.wrapping_add(3 as libc::c_int as libc::c_uint) &
+               !(3 as libc::c_int as libc::c_uint);
+}
+
+pub(crate) unsafe fn overflow_page_next(mut page: *mut libc::c_void)
+ -> *mut uint32_t {
+    return page.offset(OVERFLOW_PAGE_NEXT_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_version(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_VERSION_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_page_size(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_PAGE_SIZE_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_root_page(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_ROOT_PAGE_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_freelist_trunk(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_FREELIST_TRUNK_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_freelist_count(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_FREELIST_COUNT_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn file_header_change_counter(mut header: *mut libc::c_void)
+ -> *mut uint32_t {
+    return header.offset(FILE_HEADER_CHANGE_COUNTER_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn initialize_file_header(mut pager: *mut Pager,
+                                     mut header: *mut libc::c_void) {
+    /* The root page is the catalog's, see db_initialize */
+    memset(header, 0 as libc::c_int, (*pager).page_size as libc::c_ulong);
+    memcpy(header.offset(FILE_HEADER_MAGIC_OFFSET as isize),
+           FILE_HEADER_MAGIC.as_ptr() as *const libc::c_void,
+           FILE_HEADER_MAGIC_SIZE as libc::c_ulong);
+    *file_header_version(header) = FILE_FORMAT_VERSION;
+    *file_header_page_size(header) = (*pager).page_size;
+}
+
+pub(crate) unsafe fn check_file_header(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Refuse anything that was not written by a compatible bizdb
+  before a single page of it is touched. The page size is taken
+  from the header, the database keeps the one it was created with.
+  */
+    let mut fd: libc::c_int = (*pager).file_descriptor;
+    let mut header: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               FILE_HEADER_SIZE as libc::c_ulong);
+    lseek(fd, 0 as libc::c_int as __off_t, 0 as libc::c_int);
+    let mut bytes_read: ssize_t =
+        read(fd, header, FILE_HEADER_SIZE as size_t);
+    if bytes_read == -(1 as libc::c_int) as libc::c_long {
+        let mut error: Error = io_error("reading file header");
+        free(header);
+        return Err(error)
+    }
+    let mut result: Result<(), Error> = Ok(());
+    if bytes_read < FILE_HEADER_SIZE as libc::c_long ||
+           memcmp(header.offset(FILE_HEADER_MAGIC_OFFSET as isize),
+                  FILE_HEADER_MAGIC.as_ptr() as *const libc::c_void,
+                  FILE_HEADER_MAGIC_SIZE as libc::c_ulong) !=
+               0 as libc::c_int {
+        if is_legacy_file(fd, (*pager).file_length) {
+            result = Err(Error::Version(LEGACY_FORMAT_VERSION))
+        } else { result = Err(Error::NotADatabase) }
+    } else if *file_header_version(header) != FILE_FORMAT_VERSION {
+        result = Err(Error::Version(*file_header_version(header)))
+    } else if !page_size_valid(*file_header_page_size(header)) {
+        result = Err(Error::PageSize(*file_header_page_size(header)))
+    } else { pager_set_page_size(pager, *file_header_page_size(header)); }
+    free(header);
+    return result;
+}
+
+pub(crate) unsafe fn is_legacy_root(mut fd: libc::c_int,
+                                     mut page_num: uint32_t) -> bool {
+    let mut node: [uint8_t; 2] = [0; 2];
+    lseek(fd,
+          (page_num as __off_t) * (LEGACY_PAGE_SIZE as __off_t),
+          0 as libc::c_int);
+    if read(fd, node.as_mut_ptr() as *mut libc::c_void,
+            2 as libc::c_int as size_t) != 2 as libc::c_int as libc::c_long {
+        return 0 as libc::c_int != 0
+    }
+    return (node[0] as NodeType == NODE_LEAF ||
+                node[0] as NodeType == NODE_INTERNAL) &&
+               node[1] as libc::c_int == 1 as libc::c_int;
+}
+
+pub(crate) unsafe fn is_legacy_file(mut fd: libc::c_int,
+                                    mut file_length: off_t) -> bool {
+    /* See LEGACY_FORMAT_VERSION, only whole pages were ever written */
+    if file_length == 0 as libc::c_int as libc::c_long ||
+           file_length % LEGACY_PAGE_SIZE as libc::c_long !=
+               0 as libc::c_int as libc::c_long {
+        return 0 as libc::c_int != 0
+    }
+    if is_legacy_root(fd, 0 as libc::c_int as uint32_t) {
+        return 1 as libc::c_int != 0
+    }
+    /* The free-list header only used its first two fields */
+    let mut header: [uint8_t; 4096] = [0; 4096];
+    lseek(fd, 0 as libc::c_int as __off_t, 0 as libc::c_int);
+    if read(fd, header.as_mut_ptr() as *mut libc::c_void,
+            LEGACY_PAGE_SIZE as size_t) != LEGACY_PAGE_SIZE as libc::c_long {
+        return 0 as libc::c_int != 0
+    }
+    return header[8..].iter().all(|byte| *byte == 0) &&
+               is_legacy_root(fd, 1 as libc::c_int as uint32_t);
+}
+
+pub(crate) unsafe fn pager_adopt_page_size(mut pager: *mut Pager,
+                                    mut page_size: uint32_t) -> bool {
+    /*
+  Whether a WAL or journal written with page_size belongs to this
+  database. While the file is still empty, it may have been left
+  by the connection that created the database with a page size
+  other than the one this connection asked for: that one wins.
+  */
+    if page_size == (*pager).page_size { return 1 as libc::c_int != 0 }
+    if (*pager).file_length != 0 as libc::c_int as libc::c_long ||
+           (*pager).num_cached != 0 as libc::c_int as libc::c_uint ||
+           !page_size_valid(page_size) {
+        return 0 as libc::c_int != 0
+    }
+    pager_set_page_size(pager, page_size);
+    return 1 as libc::c_int != 0;
+}
+
+pub(crate) unsafe fn page_size_valid(mut page_size: uint32_t) -> bool {
+    return page_size >= MIN_PAGE_SIZE && page_size <= MAX_PAGE_SIZE &&
+               page_size & page_size.wrapping_sub(1 as libc::c_int as
+                                                      libc::c_uint) ==
+                   0 as libc::c_int as libc::c_uint;
+}
+
+pub(crate) unsafe fn pager_set_page_size(mut pager: *mut Pager,
+                                  mut page_size: uint32_t) {
+    (*pager).page_size = page_size;
+    (*pager).usable_size = page_size.wrapping_sub(PAGE_CHECKSUM_SIZE);
+    (*pager).leaf_node_space_for_cells =
+        (*pager).usable_size.wrapping_sub(LEAF_NODE_HEADER_SIZE);
+    /* Largest cell, pointer not included */
+    let mut max_cell_size: uint32_t =
+        (*pager).leaf_node_space_for_cells.wrapping_div(LEAF_NODE_MIN_MAX_CELLS).wrapping_sub(LEAF_NODE_CELL_POINTER_SIZE)
+            & !(3 as libc::c_int as libc::c_uint);
+    (*pager).leaf_node_max_local =
+        max_cell_size.wrapping_sub(LEAF_NODE_CELL_HEADER_SIZE);
+    (*pager).leaf_node_spill_local =
+        (*pager).leaf_node_max_local.wrapping_sub(LEAF_NODE_OVERFLOW_SIZE);
+    (*pager).leaf_node_min_fill =
+        (*pager).leaf_node_space_for_cells.wrapping_div(LEAF_NODE_MIN_MAX_CELLS);
+    (*pager).overflow_page_space =
+        (*pager).usable_size.wrapping_sub(OVERFLOW_PAGE_HEADER_SIZE);
+    (*pager).freelist_trunk_max_leaves =
+        (*pager).usable_size.wrapping_sub(FREELIST_TRUNK_HEADER_SIZE).wrapping_div(FREELIST_TRUNK_LEAF_SIZE);
+    (*pager).internal_node_max_cells =
+        (*pager).usable_size.wrapping_sub(INTERNAL_NODE_HEADER_SIZE).wrapping_div(INTERNAL_NODE_CELL_SIZE);
+    (*pager).internal_node_min_cells =
+        (*pager).internal_node_max_cells.wrapping_div(2 as libc::c_int as
+                                                          libc::c_uint);
+}
+
+pub(crate) unsafe fn freelist_trunk_next(mut trunk: *mut libc::c_void)
+ -> *mut uint32_t {
+    return trunk.offset(FREELIST_TRUNK_NEXT_OFFSET as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn freelist_trunk_num_leaves(mut trunk: *mut libc::c_void)
+ -> *mut uint32_t {
+    return trunk.offset(FREELIST_TRUNK_NUM_LEAVES_OFFSET as isize) as
+               *mut uint32_t;
+}
+
+pub(crate) unsafe fn freelist_trunk_leaf(mut trunk: *mut libc::c_void,
+                                  mut leaf_num: uint32_t) -> *mut uint32_t {
+    return trunk.offset(FREELIST_TRUNK_HEADER_SIZE as
+                            isize).offset(leaf_num.wrapping_mul(FREELIST_TRUNK_LEAF_SIZE)
+                                              as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn page_checksum(mut pager: *mut Pager,
+                            mut page: *mut libc::c_void) -> *mut uint32_t {
+    return page.offset((*pager).usable_size as isize) as *mut uint32_t;
+}
+
+pub(crate) unsafe fn crc32(mut data: *const libc::c_void, mut size: uint32_t)
+ -> uint32_t {
+    let mut bytes: *const uint8_t = data as *const uint8_t;
+    let mut crc: uint32_t = 0xffffffff as libc::c_uint;
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < size {
+        crc =
+            CRC32_TABLE[((crc ^ *bytes.offset(i as isize) as libc::c_uint) &
+                             0xff as libc::c_int as libc::c_uint) as usize] ^
+                crc >> 8 as libc::c_int;
+        i = i.wrapping_add(1)
+    }
+    return crc ^ 0xffffffff as libc::c_uint;
+}
+
+pub(crate) unsafe fn page_seal(mut pager: *mut Pager, mut page: *mut libc::c_void) {
+    /* Called on every page on its way to the WAL or database file */
+    *page_checksum(pager, page) = crc32(page, (*pager).usable_size);
+}
+
+pub(crate) unsafe fn page_verify(mut pager: *mut Pager, mut page: *mut libc::c_void)
+ -> bool {
+    return *page_checksum(pager, page) == crc32(page, (*pager).usable_size);
+}
+
+pub(crate) unsafe fn get_node_max_key(mut pager: *mut Pager,
+                                mut page_num: uint32_t)
+ -> Result<uint32_t, Error> {
+    let mut node: *mut libc::c_void = get_node(pager, page_num)?;
+    if get_node_type(node) == NODE_INTERNAL {
+        /*
+  The keys of an internal node only cover its left children,
+  so the maximum lives at the bottom of the right child.
+  */
+        return get_node_max_key(pager, *internal_node_right_child(node))
+    }
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    if num_cells == 0 as libc::c_int as libc::c_uint ||
+           !leaf_node_cell_ok(pager, node,
+                              num_cells.wrapping_sub(1 as libc::c_int as
+                                                         libc::c_uint)) {
+        /* Only an empty table has an empty leaf, and it has no maximum */
+        return Err(Error::Corrupt(page_num))
+    }
+    return Ok(*leaf_node_key(node,
+                             num_cells.wrapping_sub(1 as libc::c_int as
+                                                        libc::c_uint)));
+}
+
+pub(crate) unsafe fn print_constants(mut pager: *mut Pager,
+                                     out: &mut String) {
+    writeln!(out, "PAGE_SIZE: {}", (*pager).page_size).ok();
+    writeln!(out, "COMMON_NODE_HEADER_SIZE: {}",
+             COMMON_NODE_HEADER_SIZE as libc::c_int).ok();
+    writeln!(out, "LEAF_NODE_HEADER_SIZE: {}", { LEAF_NODE_HEADER_SIZE }).ok();
+    writeln!(out, "LEAF_NODE_CELL_HEADER_SIZE: {}",
+             { LEAF_NODE_CELL_HEADER_SIZE }).ok();
+    writeln!(out, "LEAF_NODE_SPACE_FOR_CELLS: {}",
+             (*pager).leaf_node_space_for_cells).ok();
+    writeln!(out, "LEAF_NODE_MAX_LOCAL: {}",
+             (*pager).leaf_node_max_local).ok();
+    writeln!(out, "OVERFLOW_PAGE_SPACE: {}",
+             (*pager).overflow_page_space).ok();
+}
+
+pub(crate) unsafe fn pager_lookup(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> *mut CachedPage {
+    let mut cached: *mut CachedPage =
+        (*pager).buckets[(page_num as usize).wrapping_rem(PAGER_HASH_BUCKETS)];
+    while !cached.is_null() && (*cached).page_num != page_num {
+        cached = (*cached).hash_next
+    }
+    return cached;
+}
+
+pub(crate) unsafe fn pager_lru_unlink(mut pager: *mut Pager,
+                               mut cached: *mut CachedPage) {
+    if (*cached).lru_prev.is_null() {
+        (*pager).lru_head = (*cached).lru_next
+    } else { (*(*cached).lru_prev).lru_next = (*cached).lru_next }
+    if (*cached).lru_next.is_null() {
+        (*pager).lru_tail = (*cached).lru_prev
+    } else { (*(*cached).lru_next).lru_prev = (*cached).lru_prev }
+    (*cached).lru_prev = 0 as *mut CachedPage;
+    (*cached).lru_next = 0 as *mut CachedPage;
+}
+
+pub(crate) unsafe fn pager_lru_push_front(mut pager: *mut Pager,
+                                   mut cached: *mut CachedPage) {
+    (*cached).lru_prev = 0 as *mut CachedPage;
+    (*cached).lru_next = (*pager).lru_head;
+    if (*pager).lru_head.is_null() {
+        (*pager).lru_tail = cached
+    } else { (*(*pager).lru_head).lru_prev = cached }
+    (*pager).lru_head = cached;
+}
+
+pub(crate) unsafe fn pager_hash_remove(mut pager: *mut Pager,
+                                mut cached: *mut CachedPage) {
+    let mut link: *mut *mut CachedPage =
+        &mut *(*pager).buckets.as_mut_ptr().offset(((*cached).page_num as
+                                                        usize).wrapping_rem(PAGER_HASH_BUCKETS)
+                                                       as isize) as
+            *mut *mut CachedPage;
+    while *link != cached { link = &mut (**link).hash_next }
+    *link = (*cached).hash_next;
+    (*cached).hash_next = 0 as *mut CachedPage;
+}
+
+pub(crate) unsafe fn pager_release(mut pager: *mut Pager) {
+    /*
+  Called at points where nobody holds a page pointer.
+  Pages handed out before this call may be evicted again.
+  */
+    (*pager).generation = (*pager).generation.wrapping_add(1);
+}
+
+pub(crate) unsafe fn pager_evict(mut pager: *mut Pager)
+ -> Result<*mut CachedPage, Error> {
+    /*
+  Spill (if dirty) and detach the least recently used page.
+  Pages handed out since the last pager_release may still be
+  referenced by the caller, so they are never evicted; when
+  only such pages are cached, return NULL and let the cache grow.
+  */
+    let mut victim: *mut CachedPage = (*pager).lru_tail;
+    if victim.is_null() || (*victim).generation == (*pager).generation {
+        return Ok(0 as *mut CachedPage)
+    }
+    if (*victim).dirty {
+        if (*pager).journal_mode == JournalMode::Rollback {
+            /* The originals must be safe before the file is touched */
+            journal_sync(pager)?;
+            page_seal(pager, (*victim).data);
+            pager_write_page(pager, (*victim).page_num, (*victim).data)?;
+            (*victim).dirty = 0 as libc::c_int != 0
+        } else {
+            wal_write_frame(pager, victim, 0 as libc::c_int as uint32_t)?;
+        }
+    }
+    pager_lru_unlink(pager, victim);
+    pager_hash_remove(pager, victim);
+    (*pager).num_cached = (*pager).num_cached.wrapping_sub(1);
+    return Ok(victim);
+}
+
+pub(crate) unsafe fn sidecar_path(mut filename: *const libc::c_char,
+                           mut suffix: *const libc::c_char)
+ -> *mut libc::c_char {
+    let mut path: *mut libc::c_char =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               strlen(filename).wrapping_add(strlen(suffix)).wrapping_add(1 as
+                                                                              libc::c_int
+                                                                              as
+                                                                              libc::c_ulong))
+            as *mut libc::c_char;
+    strcpy(path, filename);
+    strcat(path, suffix);
+    return path;
+}
+
+pub(crate) unsafe fn pager_write(mut pager: *mut Pager, mut fd: int,
+                          mut buffer: *const libc::c_void, mut size: size_t)
+ -> ssize_t {
+    /*
+  Every write to the database, WAL or journal goes through here,
+  so .torn_write can cut one short the way a power loss would.
+  The pager then fails every write, sync, truncate and delete,
+  leaving the files as they were at the moment of the crash.
+  */
+    if (*pager).crashed { return pager_crashed() as ssize_t }
+    #[cfg(feature = "torn-write")]
+    {
+        if (*pager).torn_write_countdown > 0 as libc::c_int as libc::c_uint {
+            (*pager).torn_write_countdown =
+                (*pager).torn_write_countdown.wrapping_sub(1);
+            if (*pager).torn_write_countdown ==
+                   0 as libc::c_int as libc::c_uint {
+                write(fd, buffer,
+                      size.wrapping_div(2 as libc::c_int as size_t));
+                (*pager).crashed = 1 as libc::c_int != 0;
+                return pager_crashed() as ssize_t
+            }
+        }
+    }
+    /* A write can stop early, say on a signal; carry on from there */
+    let mut written: size_t = 0 as libc::c_int as size_t;
+    while written < size {
+        let mut bytes_written: ssize_t =
+            write(fd, (buffer as *const libc::c_char).add(written as usize)
+                      as *const libc::c_void, size.wrapping_sub(written));
+        if bytes_written == -(1 as libc::c_int) as libc::c_long {
+            return bytes_written
+        }
+        if bytes_written == 0 as libc::c_int as libc::c_long { break ; }
+        written = written.wrapping_add(bytes_written as size_t)
+    }
+    return written as ssize_t;
+}
+
+pub(crate) unsafe fn write_result(mut bytes_written: ssize_t,
+                                  mut size: size_t,
+                                  mut action: &'static str)
+ -> Result<(), Error> {
+    /* What pager_write returned, as an error unless all of it was written */
+    if bytes_written == -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error(action))
+    }
+    if bytes_written as size_t != size {
+        return Err(Error::Io(action, libc::EIO))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_crashed() -> libc::c_int {
+    /* What a system call on a crashed pager returns */
+    *__errno_location() = libc::EIO;
+    return -(1 as libc::c_int);
+}
+
+pub(crate) unsafe fn pager_truncate(mut pager: *mut Pager, mut fd: int,
+                                    mut length: off_t) -> libc::c_int {
+    if (*pager).crashed { return pager_crashed() }
+    return ftruncate(fd, length);
+}
+
+pub(crate) unsafe fn pager_unlink(mut pager: *mut Pager,
+                                  mut filename: *const libc::c_char)
+ -> libc::c_int {
+    if (*pager).crashed { return pager_crashed() }
+    return unlink(filename);
+}
+
+pub(crate) unsafe fn pager_fsync(mut pager: *mut Pager, mut fd: int,
+                          mut level: Synchronous, mut data_only: bool)
+ -> libc::c_int {
+    /*
+  Sync a file if the synchronous setting is at least `level`.
+  The logs only ever need their data and size on disk, which
+  fdatasync provides without flushing the rest of the inode.
+  */
+    if (*pager).synchronous < level { return 0 as libc::c_int }
+    if (*pager).crashed { return pager_crashed() }
+    if data_only { return fdatasync(fd) }
+    return fsync(fd);
+}
+
+pub(crate) unsafe fn io_error(mut action: &'static str) -> Error {
+    /* Called right after the failing call, while errno is still its own */
+    let mut errno: libc::c_int = *__errno_location();
+    if errno == libc::ENOSPC { return Error::Full }
+    return Error::Io(action, errno);
+}
+
+pub(crate) unsafe fn pager_sync_directory(mut pager: *mut Pager,
+                                   mut level: Synchronous) {
+    /*
+  Creating, deleting or renaming a file only survives a power
+  loss once its directory is synced. Some file systems cannot
+  sync a directory, which is not worth failing over.
+  */
+    if (*pager).synchronous < level { return }
+    let mut directory: *mut libc::c_char = strdup((*pager).filename);
+    let mut slash: *mut libc::c_char =
+        strrchr(directory, '/' as i32);
+    let mut fd: libc::c_int = 0;
+    if slash.is_null() {
+        fd =
+            open(b".\x00" as *const u8 as *const libc::c_char,
+                 0 as libc::c_int, 0 as libc::c_int)
+    } else {
+        *slash.offset(1 as libc::c_int as isize) =
+            0 as libc::c_int as libc::c_char;
+        fd = open(directory, 0 as libc::c_int, 0 as libc::c_int)
+    }
+    if fd != -(1 as libc::c_int) { fsync(fd); close(fd); }
+    free(directory as *mut libc::c_void);
+}
+
+pub(crate) unsafe fn lock_byte(mut fd: int, mut lock_type: libc::c_short,
+                        mut offset: off_t) -> bool {
+    /* F_OFD_SETLK on one byte, fails at once if another pager conflicts */
+    let mut lock: libc::flock = ::std::mem::zeroed();
+    lock.l_type = lock_type;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    lock.l_start = offset;
+    lock.l_len = 1 as libc::c_int as off_t;
+    return libc::fcntl(fd, libc::F_OFD_SETLK, &mut lock) !=
+               -(1 as libc::c_int);
+}
+
+pub(crate) unsafe fn pager_lock_byte(mut pager: *mut Pager,
+                              mut lock_type: libc::c_short, mut offset: off_t,
+                              mut busy: bool, mut waited: *mut uint32_t)
+ -> bool {
+    /*
+  Retry every millisecond while another pager holds the byte,
+  until the busy timeout has passed. waited is shared by the
+  steps of one pager_lock call, which all count against it.
+  */
+    loop  {
+        if lock_byte((*pager).file_descriptor, lock_type, offset) {
+            return 1 as libc::c_int != 0
+        }
+        if !busy || *waited >= (*pager).busy_timeout {
+            return 0 as libc::c_int != 0
+        }
+        usleep(1000 as libc::c_int as libc::c_uint);
+        *waited = (*waited).wrapping_add(1)
+    };
+}
+
+pub(crate) unsafe fn pager_pending_held(mut pager: *mut Pager) -> bool {
+    /* Whether another connection is waiting for readers to finish */
+    let mut fd: int = (*pager).file_descriptor;
+    if !lock_byte(fd, libc::F_RDLCK as libc::c_short, LOCK_BYTE_PENDING) {
+        return 1 as libc::c_int != 0
+    }
+    lock_byte(fd, libc::F_UNLCK as libc::c_short, LOCK_BYTE_PENDING);
+    return 0 as libc::c_int != 0;
+}
+
+pub(crate) unsafe fn pager_lock(mut pager: *mut Pager, mut level: LockLevel,
+                         mut busy: bool) -> bool {
+    /*
+  Raise the lock on the database file. With busy set, locks held
+  by other connections are waited for up to the busy timeout. On
+  failure the levels already reached are kept.
+  A writer waiting for readers to finish holds RESERVED, so a
+  reader must never wait for RESERVED while keeping its read
+  lock. From NONE, RESERVED is queued for before SHARED; from
+  SHARED the wait stops once someone holds PENDING.
+  */
+    let mut waited: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut reserved: bool = 0 as libc::c_int != 0;
+    if (*pager).lock == LOCK_NONE && level >= LOCK_RESERVED {
+        if !pager_lock_byte(pager, libc::F_WRLCK as libc::c_short,
+                            LOCK_BYTE_RESERVED, busy, &mut waited) {
+            return 0 as libc::c_int != 0
+        }
+        reserved = 1 as libc::c_int != 0
+    }
+    if (*pager).lock == LOCK_NONE && level >= LOCK_SHARED {
+        /* A writer holding PENDING keeps new readers out */
+        let mut locked: bool =
+            pager_lock_byte(pager, libc::F_RDLCK as libc::c_short,
+                            LOCK_BYTE_PENDING, busy, &mut waited);
+        if locked {
+            locked =
+                pager_lock_byte(pager, libc::F_RDLCK as libc::c_short,
+                                LOCK_BYTE_SHARED, busy, &mut waited);
+            lock_byte((*pager).file_descriptor,
+                      libc::F_UNLCK as libc::c_short, LOCK_BYTE_PENDING);
+        }
+        if !locked {
+            if reserved {
+                lock_byte((*pager).file_descriptor,
+                          libc::F_UNLCK as libc::c_short,
+                          LOCK_BYTE_RESERVED);
+            }
+            return 0 as libc::c_int != 0
+        }
+        (*pager).lock = LOCK_SHARED;
+        if reserved { (*pager).lock = LOCK_RESERVED }
+    }
+    if (*pager).lock == LOCK_SHARED && level >= LOCK_RESERVED {
+        while !lock_byte((*pager).file_descriptor,
+                         libc::F_WRLCK as libc::c_short,
+                         LOCK_BYTE_RESERVED) {
+            if !busy || waited >= (*pager).busy_timeout ||
+                   pager_pending_held(pager) {
+                return 0 as libc::c_int != 0
+            }
+            usleep(1000 as libc::c_int as libc::c_uint);
+            waited = waited.wrapping_add(1)
+        }
+        (*pager).lock = LOCK_RESERVED
+    }
+    if (*pager).lock == LOCK_RESERVED && level >= LOCK_EXCLUSIVE {
+        if !pager_lock_byte(pager, libc::F_WRLCK as libc::c_short,
+                            LOCK_BYTE_PENDING, busy, &mut waited) {
+            return 0 as libc::c_int != 0
+        }
+        if !pager_lock_byte(pager, libc::F_WRLCK as libc::c_short,
+                            LOCK_BYTE_SHARED, busy, &mut waited) {
+            lock_byte((*pager).file_descriptor,
+                      libc::F_UNLCK as libc::c_short, LOCK_BYTE_PENDING);
+            return 0 as libc::c_int != 0
+        }
+        (*pager).lock = LOCK_EXCLUSIVE
+    }
+    return 1 as libc::c_int != 0;
+}
+
+pub(crate) unsafe fn pager_unlock(mut pager: *mut Pager, mut level: LockLevel) {
+    /* Drop back to SHARED or NONE */
+    let mut fd: int = (*pager).file_descriptor;
+    if (*pager).lock <= level { return }
+    if level == LOCK_SHARED {
+        /* Turning the write lock into a read lock never waits */
+        lock_byte(fd, libc::F_RDLCK as libc::c_short, LOCK_BYTE_SHARED);
+    } else {
+        lock_byte(fd, libc::F_UNLCK as libc::c_short, LOCK_BYTE_SHARED);
+    }
+    lock_byte(fd, libc::F_UNLCK as libc::c_short, LOCK_BYTE_PENDING);
+    lock_byte(fd, libc::F_UNLCK as libc::c_short, LOCK_BYTE_RESERVED);
+    if level == LOCK_NONE && (*pager).read_mark != -(1 as libc::c_int) as off_t
+       {
+        lock_byte(fd, libc::F_UNLCK as libc::c_short, (*pager).read_mark);
+        (*pager).read_mark = -(1 as libc::c_int) as off_t
+    }
+    (*pager).lock = level;
+}
+
+pub(crate) unsafe fn pager_only_connection(mut pager: *mut Pager) -> bool {
+    /*
+  Every connection read-locks the CONNECTION byte for as long as
+  it has the file open, so a write lock on it only succeeds when
+  no other connection has.
+  */
+    let mut fd: int = (*pager).file_descriptor;
+    if !lock_byte(fd, libc::F_WRLCK as libc::c_short,
+                  LOCK_BYTE_CONNECTION) {
+        return 0 as libc::c_int != 0
+    }
+    lock_byte(fd, libc::F_RDLCK as libc::c_short, LOCK_BYTE_CONNECTION);
+    return 1 as libc::c_int != 0;
+}
+
+pub(crate) unsafe fn lock_held(mut fd: int, mut offset: off_t, mut length: off_t)
+ -> off_t {
+    /*
+  F_OFD_GETLK over a range of bytes, a length of 0 reaching to the
+  end: where one of the locks other pagers hold in it starts,
+  not necessarily the first one, or -1 if there are none.
+  */
+    let mut lock: libc::flock = ::std::mem::zeroed();
+    lock.l_type = libc::F_WRLCK as libc::c_short;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    lock.l_start = offset;
+    lock.l_len = length;
+    if libc::fcntl(fd, libc::F_OFD_GETLK, &mut lock) ==
+           -(1 as libc::c_int) ||
+           lock.l_type == libc::F_UNLCK as libc::c_short {
+        return -(1 as libc::c_int) as off_t
+    }
+    return lock.l_start;
+}
+
+pub(crate) unsafe fn pager_set_read_mark(mut pager: *mut Pager,
+                                  mut frames: uint32_t) {
+    /* Nobody write-locks a read mark, so this never has to wait */
+    let mut mark: off_t = LOCK_BYTE_READ_MARK + frames as off_t;
+    if mark == (*pager).read_mark { return }
+    lock_byte((*pager).file_descriptor, libc::F_RDLCK as libc::c_short,
+              mark);
+    if (*pager).read_mark != -(1 as libc::c_int) as off_t {
+        lock_byte((*pager).file_descriptor, libc::F_UNLCK as libc::c_short,
+                  (*pager).read_mark);
+    }
+    (*pager).read_mark = mark;
+}
+
+pub(crate) unsafe fn pager_oldest_read_mark(mut pager: *mut Pager,
+                                     mut frames: uint32_t) -> uint32_t {
+    /*
+  The shortest snapshot another connection is reading, or frames
+  if every snapshot is at least that long. Each lock found narrows
+  the search to the marks below it.
+  */
+    let mut oldest: uint32_t = frames;
+    while oldest > 0 as libc::c_int as libc::c_uint {
+        let mut start: off_t =
+            lock_held((*pager).file_descriptor, LOCK_BYTE_READ_MARK,
+                      oldest as off_t);
+        if start == -(1 as libc::c_int) as off_t { break ; }
+        oldest = (start - LOCK_BYTE_READ_MARK) as uint32_t
+    }
+    return oldest;
+}
+
+pub(crate) unsafe fn wal_checksum(mut data: *const libc::c_void, mut size: uint32_t,
+                           mut checksum: *mut uint32_t) {
+    /*
+  Fletcher-style sum over 32-bit words. It continues from the
+  value already in checksum, so every frame also covers the
+  frames before it.
+  */
+    let mut words: *const uint32_t = data as *const uint32_t;
+    let mut num_words: uint32_t =
+        size.wrapping_div(4 as libc::c_int as libc::c_uint);
+    let mut s1: uint32_t = *checksum.offset(0 as libc::c_int as isize);
+    let mut s2: uint32_t = *checksum.offset(1 as libc::c_int as isize);
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i.wrapping_add(1 as libc::c_int as libc::c_uint) < num_words {
+        s1 = s1.wrapping_add(*words.offset(i as isize)).wrapping_add(s2);
+        s2 =
+            s2.wrapping_add(*words.offset(i.wrapping_add(1 as libc::c_int as
+                                                             libc::c_uint) as
+                                              isize)).wrapping_add(s1);
+        i = i.wrapping_add(2 as libc::c_int as libc::c_uint)
+    }
+    *checksum.offset(0 as libc::c_int as isize) = s1;
+    *checksum.offset(1 as libc::c_int as isize) = s2;
+}
+
+pub(crate) unsafe fn wal_frame_offset(mut pager: *mut Pager, mut frame: uint32_t)
+ -> off_t {
+    return WAL_HEADER_SIZE as off_t +
+               (frame as off_t) *
+                   (WAL_FRAME_HEADER_SIZE.wrapping_add((*pager).page_size) as off_t);
+}
+
+pub(crate) unsafe fn page_index_lookup(mut index: *mut *mut PageIndexEntry,
+                                mut page_num: uint32_t)
+ -> *mut PageIndexEntry {
+    let mut entry: *mut PageIndexEntry =
+        *index.offset((page_num as usize).wrapping_rem(PAGER_HASH_BUCKETS) as
+                          isize);
+    while !entry.is_null() && (*entry).page_num != page_num {
+        entry = (*entry).next
+    }
+    return entry;
+}
+
+pub(crate) unsafe fn page_index_set(mut index: *mut *mut PageIndexEntry,
+                             mut page_num: uint32_t, mut slot: uint32_t) {
+    let mut entry: *mut PageIndexEntry = page_index_lookup(index, page_num);
+    if entry.is_null() {
+        let mut bucket: *mut *mut PageIndexEntry =
+            index.offset((page_num as usize).wrapping_rem(PAGER_HASH_BUCKETS)
+                             as isize);
+        entry =
+            calloc(1 as libc::c_int as libc::c_ulong,
+                   ::std::mem::size_of::<PageIndexEntry>() as libc::c_ulong)
+                as *mut PageIndexEntry;
+        (*entry).page_num = page_num;
+        (*entry).next = *bucket;
+        *bucket = entry
+    }
+    (*entry).slot = slot;
+}
+
+pub(crate) unsafe fn page_index_clear(mut index: *mut *mut PageIndexEntry) {
+    let mut bucket: usize = 0 as libc::c_int as usize;
+    while bucket < PAGER_HASH_BUCKETS {
+        let mut entry: *mut PageIndexEntry = *index.offset(bucket as isize);
+        while !entry.is_null() {
+            let mut next: *mut PageIndexEntry = (*entry).next;
+            free(entry as *mut libc::c_void);
+            entry = next
+        }
+        *index.offset(bucket as isize) = 0 as *mut PageIndexEntry;
+        bucket = bucket.wrapping_add(1)
+    };
+}
+
+pub(crate) unsafe fn wal_read(mut pager: *mut Pager, mut page_num: uint32_t,
+                       mut offset: off_t, mut buffer: *mut libc::c_void,
+                       mut size: uint32_t) -> Result<(), Error> {
+    /*
+  Only frames already committed are read, so a WAL that ends
+  before one does was cut short and page_num is corrupt. Frame
+  headers come before their page number is known, and pass 0.
+  */
+    if lseek((*pager).wal_fd, offset, 0 as libc::c_int) ==
+           -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("seeking WAL"))
+    }
+    let mut bytes_read: ssize_t =
+        read((*pager).wal_fd, buffer, size as size_t);
+    if bytes_read == -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("reading WAL"))
+    }
+    if bytes_read != size as libc::c_long {
+        return Err(Error::Corrupt(page_num))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn wal_write(mut pager: *mut Pager, mut offset: off_t,
+                        mut buffer: *const libc::c_void, mut size: uint32_t)
+ -> Result<(), Error> {
+    if lseek((*pager).wal_fd, offset, 0 as libc::c_int) ==
+           -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("seeking WAL"))
+    }
+    let mut bytes_written: ssize_t =
+        pager_write(pager, (*pager).wal_fd, buffer, size as size_t);
+    return write_result(bytes_written, size as size_t, "writing WAL");
+}
+
+pub(crate) unsafe fn wal_write_frame(mut pager: *mut Pager,
+                              mut cached: *mut CachedPage,
+                              mut db_size: uint32_t) -> Result<(), Error> {
+    /*
+  Append a cached page to the WAL. A non-zero db_size marks
+  the frame as the last one of a commit.
+  */
+    if (*pager).wal_fd == -(1 as libc::c_int) {
+        (*pager).wal_fd =
+            open((*pager).wal_filename,
+                 0o2 as libc::c_int | 0o100 as libc::c_int,
+                 0o200 as libc::c_int | 0o400 as libc::c_int);
+        if (*pager).wal_fd == -(1 as libc::c_int) {
+            return Err(io_error("opening WAL file"))
+        }
+        pager_sync_directory(pager, Synchronous::Full);
+    }
+    if (*pager).wal_frames == 0 as libc::c_int as libc::c_uint {
+        /*
+  First frame since the last checkpoint, start a new log. Its
+  salts must differ from those of any earlier log another
+  connection may still know, or it would read the new frames
+  as a continuation of the old ones.
+  */
+        (*pager).wal_salt[0] = (*pager).wal_salt[0].wrapping_add(1);
+        (*pager).wal_salt[1] =
+            (*pager).wal_salt[1].wrapping_mul(1103515245 as libc::c_int as
+                                                  libc::c_uint) ^
+                (getpid() as
+                     uint32_t).wrapping_mul(2654435761 as libc::c_uint) ^
+                time(0 as *mut libc::time_t) as uint32_t;
+        let mut header: [uint32_t; 8] =
+            [WAL_MAGIC, WAL_FORMAT_VERSION, (*pager).page_size,
+             (*pager).wal_checkpoint_seq, (*pager).wal_salt[0],
+             (*pager).wal_salt[1], 0 as libc::c_int as uint32_t,
+             0 as libc::c_int as uint32_t];
+        (*pager).wal_checksum = [0 as libc::c_int as uint32_t; 2];
+        wal_checksum(header.as_mut_ptr() as *const libc::c_void,
+                     WAL_HEADER_SIZE.wrapping_sub(8 as libc::c_int as
+                                                      libc::c_uint),
+                     (*pager).wal_checksum.as_mut_ptr());
+        header[6] = (*pager).wal_checksum[0];
+        header[7] = (*pager).wal_checksum[1];
+        wal_write(pager, 0 as libc::c_int as off_t,
+                  header.as_mut_ptr() as *const libc::c_void,
+                  WAL_HEADER_SIZE)?;
+    }
+    page_seal(pager, (*cached).data);
+    let mut frame_header: [uint32_t; 6] =
+        [(*cached).page_num, db_size, (*pager).wal_salt[0],
+         (*pager).wal_salt[1], 0 as libc::c_int as uint32_t,
+         0 as libc::c_int as uint32_t];
+    wal_checksum(frame_header.as_mut_ptr() as *const libc::c_void,
+                 8 as libc::c_int as uint32_t,
+                 (*pager).wal_checksum.as_mut_ptr());
+    wal_checksum((*cached).data, (*pager).page_size,
+                 (*pager).wal_checksum.as_mut_ptr());
+    frame_header[4] = (*pager).wal_checksum[0];
+    frame_header[5] = (*pager).wal_checksum[1];
+    let mut offset: off_t = wal_frame_offset(pager, (*pager).wal_frames);
+    wal_write(pager, offset, frame_header.as_mut_ptr() as *const libc::c_void,
+              WAL_FRAME_HEADER_SIZE)?;
+    wal_write(pager, offset + WAL_FRAME_HEADER_SIZE as off_t, (*cached).data,
+              (*pager).page_size)?;
+    page_index_set((*pager).wal_index.as_mut_ptr(), (*cached).page_num,
+                   (*pager).wal_frames);
+    (*pager).wal_frames = (*pager).wal_frames.wrapping_add(1);
+    (*cached).dirty = 0 as libc::c_int != 0;
+    (*pager).pages_written = (*pager).pages_written.wrapping_add(1);
+    return Ok(());
+}
+
+pub(crate) unsafe fn wal_reset(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Start the log over. New salts make any stale frames left
+  past the new end of the file fail to verify.
+  */
+    page_index_clear((*pager).wal_index.as_mut_ptr());
+    (*pager).wal_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_committed_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_db_size = 0 as libc::c_int as uint32_t;
+    (*pager).wal_backfilled = 0 as libc::c_int as uint32_t;
+    (*pager).wal_checkpoint_seq =
+        (*pager).wal_checkpoint_seq.wrapping_add(1);
+    (*pager).wal_salt[0] = (*pager).wal_salt[0].wrapping_add(1);
+    (*pager).wal_salt[1] =
+        (*pager).wal_salt[1].wrapping_mul(1103515245 as libc::c_int as
+                                              libc::c_uint).wrapping_add(12345
+                                                                             as
+                                                                             libc::c_int
+                                                                             as
+                                                                             libc::c_uint);
+    if (*pager).wal_fd != -(1 as libc::c_int) &&
+           pager_truncate(pager, (*pager).wal_fd, 0 as libc::c_int as off_t)
+               ==
+               -(1 as libc::c_int) {
+        return Err(io_error("truncating WAL"))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn wal_recover(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Replay a WAL left behind by a process that did not close the
+  database. Frames are trusted up to the last commit frame with
+  an intact checksum chain; anything after it belongs to an
+  unfinished statement and is dropped.
+  */
+    let mut fd: libc::c_int =
+        open((*pager).wal_filename, 0o2 as libc::c_int, 0 as libc::c_int);
+    if fd == -(1 as libc::c_int) { return Ok(()) }
+    (*pager).wal_fd = fd;
+    let mut header: [uint32_t; 8] = [0 as libc::c_int as uint32_t; 8];
+    let mut checksum: [uint32_t; 2] = [0 as libc::c_int as uint32_t; 2];
+    lseek(fd, 0 as libc::c_int as __off_t, 0 as libc::c_int);
+    if read(fd, header.as_mut_ptr() as *mut libc::c_void,
+            WAL_HEADER_SIZE as size_t) != WAL_HEADER_SIZE as libc::c_long ||
+           header[0] != WAL_MAGIC || header[1] != WAL_FORMAT_VERSION ||
+           !pager_adopt_page_size(pager, header[2]) {
+        return wal_reset(pager)
+    }
+    wal_checksum(header.as_mut_ptr() as *const libc::c_void,
+                 WAL_HEADER_SIZE.wrapping_sub(8 as libc::c_int as
+                                                  libc::c_uint),
+                 checksum.as_mut_ptr());
+    if checksum[0] != header[6] || checksum[1] != header[7] {
+        return wal_reset(pager)
+    }
+    (*pager).wal_checkpoint_seq = header[3];
+    (*pager).wal_salt[0] = header[4];
+    (*pager).wal_salt[1] = header[5];
+    /* Find the last commit frame */
+    let mut frame_header: [uint32_t; 6] = [0 as libc::c_int as uint32_t; 6];
+    let mut page: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    let mut num_committed: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut db_size: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut frame: uint32_t = 0 as libc::c_int as uint32_t;
+    loop  {
+        lseek(fd, wal_frame_offset(pager, frame), 0 as libc::c_int);
+        if read(fd, frame_header.as_mut_ptr() as *mut libc::c_void,
+                WAL_FRAME_HEADER_SIZE as size_t) !=
+               WAL_FRAME_HEADER_SIZE as libc::c_long ||
+               read(fd, page, (*pager).page_size as size_t) !=
+                   (*pager).page_size as libc::c_long {
+            break ;
+        }
+        if frame_header[2] != header[4] || frame_header[3] != header[5] {
+            break ;
+        }
+        wal_checksum(frame_header.as_mut_ptr() as *const libc::c_void,
+                     8 as libc::c_int as uint32_t, checksum.as_mut_ptr());
+        wal_checksum(page, (*pager).page_size, checksum.as_mut_ptr());
+        if checksum[0] != frame_header[4] || checksum[1] != frame_header[5] {
+            break ;
+        }
+        frame = frame.wrapping_add(1);
+        if frame_header[1] != 0 as libc::c_int as libc::c_uint {
+            num_committed = frame;
+            db_size = frame_header[1]
+        }
+    }
+    /* Copy in log order so later frames win */
+    let mut result: Result<(), Error> = Ok(());
+    frame = 0 as libc::c_int as uint32_t;
+    while result.is_ok() && frame < num_committed {
+        result =
+            wal_read(pager, 0 as libc::c_int as uint32_t,
+                     wal_frame_offset(pager, frame), frame_header.as_mut_ptr() as *mut libc::c_void,
+                     WAL_FRAME_HEADER_SIZE);
+        /* Pages past the committed size were dropped by ROLLBACK TO */
+        if result.is_ok() && frame_header[0] < db_size {
+            result =
+                wal_read(pager, frame_header[0],
+                         wal_frame_offset(pager, frame) +
+                             WAL_FRAME_HEADER_SIZE as off_t, page,
+                         (*pager).page_size);
+            if result.is_ok() {
+                result = pager_write_page(pager, frame_header[0], page)
+            }
+        }
+        frame = frame.wrapping_add(1)
+    }
+    free(page);
+    result?;
+    if num_committed > 0 as libc::c_int as libc::c_uint &&
+           pager_fsync(pager, (*pager).file_descriptor, Synchronous::Normal,
+                       0 as libc::c_int != 0) == -(1 as libc::c_int) {
+        return Err(io_error("syncing db file"))
+    }
+    return wal_reset(pager);
+}
+
+pub(crate) unsafe fn wal_refresh(mut pager: *mut Pager) -> Result<bool, Error> {
+    /*
+  Pick up commits other connections appended to the WAL, or
+  notice that one of them checkpointed and started it over.
+  As in wal_recover, only frames up to the last commit frame
+  with an intact checksum chain count. Returns whether the
+  committed content of the WAL changed.
+  */
+    if (*pager).wal_fd == -(1 as libc::c_int) {
+        (*pager).wal_fd =
+            open((*pager).wal_filename, 0o2 as libc::c_int,
+                 0 as libc::c_int);
+        if (*pager).wal_fd == -(1 as libc::c_int) {
+            return Ok(0 as libc::c_int != 0)
+        }
+    }
+    let mut fd: libc::c_int = (*pager).wal_fd;
+    let mut header: [uint32_t; 8] = [0 as libc::c_int as uint32_t; 8];
+    let mut checksum: [uint32_t; 2] = [0 as libc::c_int as uint32_t; 2];
+    lseek(fd, 0 as libc::c_int as __off_t, 0 as libc::c_int);
+    let mut valid: bool =
+        read(fd, header.as_mut_ptr() as *mut libc::c_void,
+             WAL_HEADER_SIZE as size_t) == WAL_HEADER_SIZE as libc::c_long &&
+            header[0] == WAL_MAGIC && header[1] == WAL_FORMAT_VERSION &&
+            pager_adopt_page_size(pager, header[2]);
+    if valid {
+        wal_checksum(header.as_mut_ptr() as *const libc::c_void,
+                     WAL_HEADER_SIZE.wrapping_sub(8 as libc::c_int as
+                                                      libc::c_uint),
+                     checksum.as_mut_ptr());
+        valid = checksum[0] == header[6] && checksum[1] == header[7]
+    }
+    let mut old_committed: uint32_t = (*pager).wal_committed_frames;
+    let mut frame: uint32_t = 0 as libc::c_int as uint32_t;
+    if valid && old_committed > 0 as libc::c_int as libc::c_uint &&
+           header[3] == (*pager).wal_checkpoint_seq &&
+           header[4] == (*pager).wal_salt[0] &&
+           header[5] == (*pager).wal_salt[1] {
+        /* Same log as last time, only look past the known commits */
+        frame = old_committed;
+        checksum = (*pager).wal_committed_checksum
+    } else {
+        page_index_clear((*pager).wal_index.as_mut_ptr());
+        (*pager).wal_frames = 0 as libc::c_int as uint32_t;
+        (*pager).wal_committed_frames = 0 as libc::c_int as uint32_t;
+        (*pager).wal_db_size = 0 as libc::c_int as uint32_t;
+        (*pager).wal_backfilled = 0 as libc::c_int as uint32_t;
+        /* Empty, or started over and not written to yet */
+        if !valid {
+            return Ok(old_committed > 0 as libc::c_int as libc::c_uint)
+        }
+        (*pager).wal_checkpoint_seq = header[3];
+        (*pager).wal_salt[0] = header[4];
+        (*pager).wal_salt[1] = header[5]
+    }
+    let mut start: uint32_t = frame;
+    let mut num_committed: uint32_t = frame;
+    let mut committed_checksum: [uint32_t; 2] = checksum;
+    let mut db_size: uint32_t = (*pager).wal_db_size;
+    let mut frame_header: [uint32_t; 6] = [0 as libc::c_int as uint32_t; 6];
+    let mut page: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    loop  {
+        lseek(fd, wal_frame_offset(pager, frame), 0 as libc::c_int);
+        if read(fd, frame_header.as_mut_ptr() as *mut libc::c_void,
+                WAL_FRAME_HEADER_SIZE as size_t) !=
+               WAL_FRAME_HEADER_SIZE as libc::c_long ||
+               read(fd, page, (*pager).page_size as size_t) !=
+                   (*pager).page_size as libc::c_long {
+            break ;
+        }
+        if frame_header[2] != header[4] || frame_header[3] != header[5] {
+            break ;
+        }
+        wal_checksum(frame_header.as_mut_ptr() as *const libc::c_void,
+                     8 as libc::c_int as uint32_t, checksum.as_mut_ptr());
+        wal_checksum(page, (*pager).page_size, checksum.as_mut_ptr());
+        if checksum[0] != frame_header[4] || checksum[1] != frame_header[5] {
+            break ;
+        }
+        frame = frame.wrapping_add(1);
+        if frame_header[1] != 0 as libc::c_int as libc::c_uint {
+            num_committed = frame;
+            committed_checksum = checksum;
+            db_size = frame_header[1]
+        }
+    }
+    free(page);
+    frame = start;
+    while frame < num_committed {
+        wal_read(pager, 0 as libc::c_int as uint32_t,
+                 wal_frame_offset(pager, frame), frame_header.as_mut_ptr() as *mut libc::c_void,
+                 WAL_FRAME_HEADER_SIZE)?;
+        page_index_set((*pager).wal_index.as_mut_ptr(), frame_header[0],
+                       frame);
+        frame = frame.wrapping_add(1)
+    }
+    (*pager).wal_frames = num_committed;
+    (*pager).wal_checksum = committed_checksum;
+    (*pager).wal_committed_frames = num_committed;
+    (*pager).wal_committed_checksum = committed_checksum;
+    (*pager).wal_db_size = db_size;
+    return Ok(num_committed != start ||
+                  start == 0 as libc::c_int as libc::c_uint &&
+                      old_committed > 0 as libc::c_int as libc::c_uint);
+}
+
+pub(crate) unsafe fn journal_write(mut pager: *mut Pager, mut offset: off_t,
+                            mut buffer: *const libc::c_void,
+                            mut size: uint32_t) -> Result<(), Error> {
+    if lseek((*pager).journal_fd, offset, 0 as libc::c_int) ==
+           -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("seeking journal"))
+    }
+    let mut bytes_written: ssize_t =
+        pager_write(pager, (*pager).journal_fd, buffer, size as size_t);
+    return write_result(bytes_written, size as size_t, "writing journal");
+}
+
+pub(crate) unsafe fn journal_open(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Start the journal of a new transaction */
+    (*pager).journal_fd =
+        open((*pager).journal_filename,
+             0o2 as libc::c_int | 0o100 as libc::c_int |
+                 0o1000 as libc::c_int,
+             0o200 as libc::c_int | 0o400 as libc::c_int);
+    if (*pager).journal_fd == -(1 as libc::c_int) {
+        return Err(io_error("opening journal file"))
+    }
+    /* A journal that vanishes on power loss could not roll anything back */
+    pager_sync_directory(pager, Synchronous::Normal);
+    (*pager).journal_records = 0 as libc::c_int as uint32_t;
+    (*pager).journal_db_size =
+        ((*pager).file_length / (*pager).page_size as off_t) as uint32_t;
+    let mut header: [uint32_t; 6] =
+        [JOURNAL_MAGIC, JOURNAL_FORMAT_VERSION, (*pager).page_size,
+         (*pager).journal_db_size, 0 as libc::c_int as uint32_t,
+         0 as libc::c_int as uint32_t];
+    let mut checksum: [uint32_t; 2] = [0 as libc::c_int as uint32_t; 2];
+    wal_checksum(header.as_mut_ptr() as *const libc::c_void,
+                 JOURNAL_HEADER_SIZE.wrapping_sub(8 as libc::c_int as
+                                                      libc::c_uint),
+                 checksum.as_mut_ptr());
+    header[4] = checksum[0];
+    header[5] = checksum[1];
+    journal_write(pager, 0 as libc::c_int as off_t,
+                  header.as_mut_ptr() as *const libc::c_void,
+                  JOURNAL_HEADER_SIZE)?;
+    (*pager).journal_synced = 0 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn journal_record_offset(mut pager: *mut Pager, mut record: uint32_t)
+ -> off_t {
+    return JOURNAL_HEADER_SIZE as off_t +
+               (record as off_t) *
+                   (JOURNAL_RECORD_HEADER_SIZE.wrapping_add((*pager).page_size) as
+                        off_t);
+}
+
+pub(crate) unsafe fn journal_page(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Save the original content of a page the first time it is
+  changed in a transaction. Until the transaction commits the
+  database file still holds that content, so it is read from
+  there rather than from the cache, which callers may already
+  have modified. Pages past the old end of the file are simply
+  cut off again on rollback.
+  */
+    if (*pager).journal_fd == -(1 as libc::c_int) { journal_open(pager)?; }
+    if page_num >= (*pager).journal_db_size ||
+           !page_index_lookup((*pager).journal_index.as_mut_ptr(),
+                              page_num).is_null() {
+        return Ok(())
+    }
+    let mut page: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    if lseek((*pager).file_descriptor,
+             (page_num as __off_t) * ((*pager).page_size as __off_t),
+             0 as libc::c_int) == -(1 as libc::c_int) as libc::c_long {
+        let mut error: Error = io_error("seeking");
+        free(page);
+        return Err(error)
+    }
+    let mut bytes_read: ssize_t =
+        read((*pager).file_descriptor, page, (*pager).page_size as size_t);
+    if bytes_read == -(1 as libc::c_int) as libc::c_long {
+        let mut error: Error = io_error("reading file");
+        free(page);
+        return Err(error)
+    }
+    /* Past the end of the file the page was never written, and is zeros */
+    if bytes_read != (*pager).page_size as libc::c_long &&
+           (page_num as off_t + 1 as libc::c_int as off_t) *
+               ((*pager).page_size as off_t) <= (*pager).file_length {
+        free(page);
+        return Err(Error::Corrupt(page_num))
+    }
+    let mut record_header: [uint32_t; 3] =
+        [page_num, 0 as libc::c_int as uint32_t,
+         0 as libc::c_int as uint32_t];
+    let mut checksum: [uint32_t; 2] =
+        [page_num, (*pager).journal_db_size];
+    wal_checksum(page, (*pager).page_size, checksum.as_mut_ptr());
+    record_header[1] = checksum[0];
+    record_header[2] = checksum[1];
+    let mut offset: off_t =
+        journal_record_offset(pager, (*pager).journal_records);
+    let mut result: Result<(), Error> =
+        journal_write(pager, offset,
+                      record_header.as_mut_ptr() as *const libc::c_void,
+                      JOURNAL_RECORD_HEADER_SIZE);
+    if result.is_ok() {
+        result =
+            journal_write(pager, offset + JOURNAL_RECORD_HEADER_SIZE as off_t,
+                          page, (*pager).page_size)
+    }
+    free(page);
+    result?;
+    page_index_set((*pager).journal_index.as_mut_ptr(), page_num,
+                   (*pager).journal_records);
+    (*pager).journal_records = (*pager).journal_records.wrapping_add(1);
+    (*pager).journal_synced = 0 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn journal_sync(mut pager: *mut Pager) -> Result<(), Error> {
+    if (*pager).journal_fd == -(1 as libc::c_int) ||
+           (*pager).journal_synced {
+        return Ok(())
+    }
+    if pager_fsync(pager, (*pager).journal_fd, Synchronous::Normal,
+                   1 as libc::c_int != 0) == -(1 as libc::c_int) {
+        return Err(io_error("syncing journal"))
+    }
+    (*pager).journal_synced = 1 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn journal_finish(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Deleting the journal is what makes the transaction durable */
+    if (*pager).journal_fd == -(1 as libc::c_int) { return Ok(()) }
+    close((*pager).journal_fd);
+    (*pager).journal_fd = -(1 as libc::c_int);
+    page_index_clear((*pager).journal_index.as_mut_ptr());
+    if pager_unlink(pager, (*pager).journal_filename) == -(1 as libc::c_int)
+       {
+        return Err(io_error("deleting journal"))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn journal_restore(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Roll back the transaction of a process that died before it
+  deleted its journal. Records are written back up to the first
+  one that fails to verify: the journal is synced before any
+  page it covers reaches the database file, so pages behind a
+  torn record were never overwritten.
+  */
+    let mut fd: libc::c_int =
+        open((*pager).journal_filename, 0o2 as libc::c_int,
+             0 as libc::c_int);
+    if fd == -(1 as libc::c_int) { return Ok(()) }
+    let mut header: [uint32_t; 6] = [0 as libc::c_int as uint32_t; 6];
+    let mut checksum: [uint32_t; 2] = [0 as libc::c_int as uint32_t; 2];
+    lseek(fd, 0 as libc::c_int as __off_t, 0 as libc::c_int);
+    let mut valid: bool =
+        read(fd, header.as_mut_ptr() as *mut libc::c_void,
+             JOURNAL_HEADER_SIZE as size_t) ==
+            JOURNAL_HEADER_SIZE as libc::c_long &&
+            header[0] == JOURNAL_MAGIC &&
+            header[1] == JOURNAL_FORMAT_VERSION &&
+            pager_adopt_page_size(pager, header[2]);
+    if valid {
+        wal_checksum(header.as_mut_ptr() as *const libc::c_void,
+                     JOURNAL_HEADER_SIZE.wrapping_sub(8 as libc::c_int as
+                                                          libc::c_uint),
+                     checksum.as_mut_ptr());
+        valid = checksum[0] == header[4] && checksum[1] == header[5]
+    }
+    let mut result: Result<(), Error> = Ok(());
+    if valid {
+        let mut db_size: uint32_t = header[3];
+        let mut record_header: [uint32_t; 3] =
+            [0 as libc::c_int as uint32_t; 3];
+        let mut page: *mut libc::c_void =
+            calloc(1 as libc::c_int as libc::c_ulong,
+                   (*pager).page_size as libc::c_ulong);
+        let mut record: uint32_t = 0 as libc::c_int as uint32_t;
+        while result.is_ok() {
+            lseek(fd, journal_record_offset(pager, record), 0 as libc::c_int);
+            if read(fd, record_header.as_mut_ptr() as *mut libc::c_void,
+                    JOURNAL_RECORD_HEADER_SIZE as size_t) !=
+                   JOURNAL_RECORD_HEADER_SIZE as libc::c_long ||
+                   read(fd, page, (*pager).page_size as size_t) !=
+                       (*pager).page_size as libc::c_long {
+                break ;
+            }
+            checksum = [record_header[0], db_size];
+            wal_checksum(page, (*pager).page_size, checksum.as_mut_ptr());
+            if checksum[0] != record_header[1] ||
+                   checksum[1] != record_header[2] {
+                break ;
+            }
+            result = pager_write_page(pager, record_header[0], page);
+            record = record.wrapping_add(1)
+        }
+        free(page);
+        /* Drop the pages the transaction appended */
+        if result.is_ok() &&
+               (*pager).file_length >
+                   (db_size as off_t) * ((*pager).page_size as off_t) {
+            if pager_truncate(pager, (*pager).file_descriptor,
+                              (db_size as off_t) *
+                                  ((*pager).page_size as off_t)) ==
+                   -(1 as libc::c_int) {
+                result = Err(io_error("truncating db file"))
+            } else {
+                (*pager).file_length =
+                    (db_size as off_t) * ((*pager).page_size as off_t)
+            }
+        }
+        if result.is_ok() &&
+               pager_fsync(pager, (*pager).file_descriptor,
+                           Synchronous::Normal, 0 as libc::c_int != 0) ==
+                   -(1 as libc::c_int) {
+            result = Err(io_error("syncing db file"))
+        }
+    }
+    close(fd);
+    /* Left for the next attempt unless every page is back */
+    if result.is_ok() { pager_unlink(pager, (*pager).journal_filename); }
+    return result;
+}
+
+pub(crate) unsafe fn pager_read_page(mut pager: *mut Pager, mut page_num: uint32_t,
+                              mut page: *mut libc::c_void)
+ -> Result<(), Error> {
+    /*
+  Load the stored version of a page, bypassing the cache, and
+  check it against its trailer so damaged bytes never reach
+  the tree code.
+  */
+    if page_num >= (*pager).num_pages {
+        /* New, or dropped by ROLLBACK TO with a stale copy left behind */
+        return Ok(())
+    }
+    let mut wal_entry: *mut PageIndexEntry =
+        page_index_lookup((*pager).wal_index.as_mut_ptr(), page_num);
+    if !wal_entry.is_null() {
+        // The WAL holds a newer copy than the database file
+        wal_read(pager, page_num,
+                 wal_frame_offset(pager, (*wal_entry).slot) +
+                     WAL_FRAME_HEADER_SIZE as off_t, page, (*pager).page_size)?;
+        (*pager).pages_read = (*pager).pages_read.wrapping_add(1)
+    } else if (page_num as off_t) * ((*pager).page_size as off_t) <
+                  (*pager).file_length {
+        // We might save a partial page at the end of the file
+        lseek((*pager).file_descriptor,
+              (page_num as __off_t) * ((*pager).page_size as __off_t),
+              0 as libc::c_int);
+        let mut bytes_read: ssize_t =
+            read((*pager).file_descriptor, page, (*pager).page_size as size_t);
+        if bytes_read == -(1 as libc::c_int) as libc::c_long {
+            return Err(io_error("reading file"))
+        }
+        (*pager).pages_read = (*pager).pages_read.wrapping_add(1);
+        if bytes_read < (*pager).page_size as libc::c_long {
+            return Err(Error::Corrupt(page_num))
+        }
+    } else {
+        /* Allocated but never written, like the root .vacuum fills last */
+        return Ok(())
+    }
+    if !page_verify(pager, page) { return Err(Error::Corrupt(page_num)) }
+    return Ok(());
+}
+
+pub(crate) unsafe fn get_page(mut pager: *mut Pager,
+                                  mut page_num: uint32_t)
+ -> Result<*mut libc::c_void, Error> {
+    let mut cached: *mut CachedPage = pager_lookup(pager, page_num);
+    if cached.is_null() {
+        // Cache miss. Reuse an evicted frame or allocate a new one
+        // and load from file. The cache grows while every page is in
+        // use, so evict until it is back below cache_size.
+        while (*pager).num_cached >= (*pager).cache_size {
+            if !cached.is_null() {
+                free((*cached).data);
+                free(cached as *mut libc::c_void);
+            }
+            cached = pager_evict(pager)?;
+            if cached.is_null() { break ; }
+        }
+        if cached.is_null() {
+            cached =
+                calloc(1 as libc::c_int as libc::c_ulong,
+                       ::std::mem::size_of::<CachedPage>() as libc::c_ulong)
+                    as *mut CachedPage;
+            (*cached).data = calloc(1 as libc::c_int as libc::c_ulong,
+                                    (*pager).page_size as libc::c_ulong)
+        }
+        (*cached).page_num = page_num;
+        (*cached).dirty = 0 as libc::c_int != 0;
+        if let Err(error) = pager_read_page(pager, page_num, (*cached).data) {
+            free((*cached).data);
+            free(cached as *mut libc::c_void);
+            return Err(error)
+        }
+        let mut bucket: usize =
+            (page_num as usize).wrapping_rem(PAGER_HASH_BUCKETS);
+        (*cached).hash_next = (*pager).buckets[bucket];
+        (*pager).buckets[bucket] = cached;
+        pager_lru_push_front(pager, cached);
+        (*pager).num_cached = (*pager).num_cached.wrapping_add(1);
+        if page_num >= (*pager).num_pages {
+            (*pager).num_pages =
+                page_num.wrapping_add(1 as libc::c_int as libc::c_uint)
+        }
+    } else if cached != (*pager).lru_head {
+        pager_lru_unlink(pager, cached);
+        pager_lru_push_front(pager, cached);
+    }
+    (*cached).generation = (*pager).generation;
+    return Ok((*cached).data);
+}
+
+pub(crate) unsafe fn get_node(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> Result<*mut libc::c_void, Error> {
+    /*
+  A page of a tree, refused as corrupt unless it is a leaf or an
+  internal node whose cells fit in the page, so code walking it
+  never reads past its end. .check reads pages with get_page to
+  report every problem instead.
+  */
+    if page_num == 0 as libc::c_int as libc::c_uint ||
+           page_num >= (*pager).num_pages {
+        return Err(Error::Corrupt(page_num))
+    }
+    let mut node: *mut libc::c_void = get_page(pager, page_num)?;
+    match get_node_type(node) {
+        NODE_LEAF => {
+            if !leaf_node_fits(pager, node) {
+                return Err(Error::Corrupt(page_num))
+            }
+        }
+        NODE_INTERNAL => {
+            if *internal_node_num_keys(node) >
+                   (*pager).internal_node_max_cells {
+                return Err(Error::Corrupt(page_num))
+            }
+        }
+        _ => { return Err(Error::Corrupt(page_num)) }
+    }
+    return Ok(node);
+}
+
+pub(crate) unsafe fn pager_mark_dirty(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /* Callers modify pages they just fetched, so the page is cached */
+    let mut cached: *mut CachedPage = pager_lookup(pager, page_num);
+    if (*pager).journal_mode == JournalMode::Rollback {
+        journal_page(pager, page_num)?;
+    }
+    if (*pager).in_transaction { pager_snapshot(pager, page_num)?; }
+    (*cached).dirty = 1 as libc::c_int != 0;
+    (*pager).changed = 1 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_set_cache_size(mut pager: *mut Pager,
+                                   mut cache_size: uint32_t)
+ -> Result<(), Error> {
+    (*pager).cache_size = cache_size;
+    while (*pager).num_cached > (*pager).cache_size {
+        let mut victim: *mut CachedPage = pager_evict(pager)?;
+        if victim.is_null() { break ; }
+        free((*victim).data);
+        free(victim as *mut libc::c_void);
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn indent(out: &mut String, mut level: uint32_t) {
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < level {
+        out.push_str("  ");
+        i = i.wrapping_add(1)
+    };
+}
+
+pub(crate) unsafe fn print_tree(mut pager: *mut Pager,
+                                    mut page_num: uint32_t,
+                                    mut indentation_level: uint32_t,
+                                    out: &mut String)
+ -> Result<(), Error> {
+    let mut node: *mut libc::c_void = get_node(pager, page_num)?;
+    let mut num_keys: uint32_t = 0;
+    let mut child: uint32_t = 0;
+    match get_node_type(node) {
+        NODE_LEAF => {
+            num_keys = *leaf_node_num_cells(node);
+            indent(out, indentation_level);
+            writeln!(out, "- leaf (size {})", num_keys).ok();
+            let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+            while i < num_keys {
+                indent(out,
+                       indentation_level.wrapping_add(1 as libc::c_int as
+                                                          libc::c_uint));
+                writeln!(out, "- {}", *leaf_node_key(node, i)).ok();
+                i = i.wrapping_add(1)
+            }
+        }
+        NODE_INTERNAL => {
+            num_keys = *internal_node_num_keys(node);
+            indent(out, indentation_level);
+            writeln!(out, "- internal (size {})", num_keys).ok();
+            let mut i_0: uint32_t = 0 as libc::c_int as uint32_t;
+            while i_0 < num_keys {
+                child = *internal_node_child(node, i_0);
+                print_tree(pager, child,
+                           indentation_level.wrapping_add(1 as libc::c_int as
+                                                              libc::c_uint),
+                           out)?;
+                indent(out,
+                       indentation_level.wrapping_add(1 as libc::c_int as
+                                                          libc::c_uint));
+                writeln!(out, "- key {}", *internal_node_key(node, i_0)).ok();
+                i_0 = i_0.wrapping_add(1)
+            }
+            child = *internal_node_right_child(node);
+            print_tree(pager, child,
+                       indentation_level.wrapping_add(1 as libc::c_int as
+                                                          libc::c_uint), out)?;
+        }
+        _ => { }
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn record_put_varint(mut record: *mut Vec<u8>,
+                                mut value: uint32_t) {
+    /* 7 bits a byte, low bits first, the high bit set on all but the last */
+    while value >= 0x80 as libc::c_int as libc::c_uint {
+        (*record).push((value & 0x7f as libc::c_int as libc::c_uint |
+                            0x80 as libc::c_int as libc::c_uint) as u8);
+        value >>= 7 as libc::c_int
+    }
+    (*record).push(value as u8);
+}
+
+pub(crate) unsafe fn record_get_varint(mut record: &[u8], mut position: *mut usize)
+ -> Result<uint32_t, ()> {
+    let mut value: uint64_t = 0 as libc::c_int as uint64_t;
+    let mut shift: uint32_t = 0 as libc::c_int as uint32_t;
+    loop  {
+        if *position >= record.len() ||
+               shift > 28 as libc::c_int as libc::c_uint {
+            return Err(())
+        }
+        let mut byte: u8 = record[*position];
+        *position = (*position).wrapping_add(1);
+        value |= ((byte & 0x7f as libc::c_int as u8) as uint64_t) << shift;
+        if byte & 0x80 as libc::c_int as u8 == 0 { break ; }
+        shift = shift.wrapping_add(7 as libc::c_int as libc::c_uint)
+    }
+    if value > 0xffffffff as libc::c_uint as uint64_t { return Err(()) }
+    return Ok(value as uint32_t);
+}
+
+pub(crate) unsafe fn record_get(mut record: &[u8], mut position: *mut usize,
+                         mut size: usize) -> Result<&[u8], ()> {
+    if size > record.len().wrapping_sub(*position) { return Err(()) }
+    let mut bytes: &[u8] = &record[*position..(*position).wrapping_add(size)];
+    *position = (*position).wrapping_add(size);
+    return Ok(bytes);
+}
+
+pub(crate) unsafe fn serialize_row(mut schema: *mut Schema, mut source: *mut Row)
+ -> Vec<u8> {
+    /* The row has been checked against the schema, see row_check */
+    let mut record: Vec<u8> =
+        vec![0; (*schema).columns.len().wrapping_add(7).wrapping_div(8)];
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < (*schema).columns.len() {
+        match &*(*source).values.as_ptr().offset(i as isize) {
+            Value::Null => {
+                *record.as_mut_ptr().offset(i.wrapping_div(8) as isize) |=
+                    ((1 as libc::c_int) << i.wrapping_rem(8)) as uint8_t;
+            }
+            Value::Integer(integer) => {
+                record.extend_from_slice(&integer.to_le_bytes());
+            }
+            Value::Real(real) => {
+                record.extend_from_slice(&real.to_le_bytes());
+            }
+            Value::Text(text) => {
+                record_put_varint(&mut record, text.len() as uint32_t);
+                record.extend_from_slice(text.as_bytes());
+            }
+            Value::Blob(blob) => {
+                record_put_varint(&mut record, blob.len() as uint32_t);
+                record.extend_from_slice(blob);
+            }
+            Value::Boolean(boolean) => { record.push(*boolean as uint8_t); }
+        }
+        i = i.wrapping_add(1)
+    }
+    return record;
+}
+
+pub(crate) unsafe fn deserialize_row(mut schema: *mut Schema, mut source: &[u8],
+                              mut destination: *mut Row)
+ -> Result<(), ()> {
+    /* Err when the record does not match the schema */
+    (*destination).values.clear();
+    let mut position: usize = 0 as libc::c_int as usize;
+    let mut null_bitmap: &[u8] =
+        record_get(source, &mut position,
+                   (*schema).columns.len().wrapping_add(7).wrapping_div(8))?;
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < (*schema).columns.len() {
+        let mut column: *mut Column = (*schema).columns.as_mut_ptr().offset(i as isize);
+        if null_bitmap[i.wrapping_div(8)] as libc::c_int &
+               (1 as libc::c_int) << i.wrapping_rem(8) != 0 {
+            (*destination).values.push(Value::Null);
+            i = i.wrapping_add(1);
+            continue ;
+        }
+        match (*column).type_0 {
+            COLUMN_TYPE_INTEGER => {
+                let mut bytes: [u8; 8] = [0; 8];
+                bytes.copy_from_slice(record_get(source, &mut position,
+                                                 8 as libc::c_int as usize)?);
+                (*destination).values.push(Value::Integer(int64_t::from_le_bytes(bytes)));
+            }
+            COLUMN_TYPE_REAL => {
+                let mut bytes: [u8; 8] = [0; 8];
+                bytes.copy_from_slice(record_get(source, &mut position,
+                                                 8 as libc::c_int as usize)?);
+                (*destination).values.push(Value::Real(f64::from_le_bytes(bytes)));
+            }
+            COLUMN_TYPE_TEXT => {
+                let mut length: uint32_t =
+                    record_get_varint(source, &mut position)?;
+                let mut text: &[u8] =
+                    record_get(source, &mut position, length as usize)?;
+                (*destination).values.push(Value::Text(String::from_utf8_lossy(text).into_owned()));
+            }
+            COLUMN_TYPE_BLOB => {
+                let mut length: uint32_t =
+                    record_get_varint(source, &mut position)?;
+                let mut blob: &[u8] =
+                    record_get(source, &mut position, length as usize)?;
+                (*destination).values.push(Value::Blob(blob.to_vec()));
+            }
+            _ => {
+                let mut boolean: &[u8] =
+                    record_get(source, &mut position,
+                               1 as libc::c_int as usize)?;
+                (*destination).values.push(Value::Boolean(boolean[0] as
+                                                              libc::c_int !=
+                                                              0 as
+                                                                  libc::c_int));
+            }
+        }
+        i = i.wrapping_add(1)
+    }
+    if position != source.len() { return Err(()) }
+    return Ok(());
+}
+
+pub(crate) unsafe fn row_key(mut row: *mut Row) -> uint32_t {
+    if let Value::Integer(key) = *(*row).values.as_ptr() {
+        return key as uint32_t
+    }
+    panic!("Key column is not an integer");
+}
+
+pub(crate) unsafe fn row_check_key(mut value: *mut Value) -> PrepareResult {
+    /* The key is stored in the tree as an unsigned 32 bit number */
+    match *value {
+        Value::Null => { return PrepareResult::NullId }
+        Value::Integer(key) if key < 0 as libc::c_int as int64_t => {
+            return PrepareResult::NegativeId
+        }
+        Value::Integer(key) if key > 0xffffffff as libc::c_uint as int64_t =>
+        {
+            return PrepareResult::IdTooLarge
+        }
+        Value::Integer(_) => { return PrepareResult::Success }
+        _ => { return PrepareResult::TypeMismatch }
+    };
+}
+
+pub(crate) unsafe fn row_check(mut schema: *mut Schema, mut row: *mut Row)
+ -> PrepareResult {
+    /* Rows that do not come from the parser, see Database::insert */
+    if (*row).values.len() != (*schema).columns.len() {
+        return PrepareResult::SyntaxError
+    }
+    let mut result: PrepareResult =
+        row_check_key((*row).values.as_mut_ptr());
+    if result != PrepareResult::Success {
+        return result
+    }
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < (*schema).columns.len() {
+        let mut column: *mut Column = (*schema).columns.as_mut_ptr().offset(i as isize);
+        match &*(*row).values.as_ptr().offset(i as isize) {
+            Value::Null => { }
+            Value::Integer(_) if (*column).type_0 == COLUMN_TYPE_INTEGER => { }
+            Value::Real(real) if (*column).type_0 == COLUMN_TYPE_REAL => {
+                if !real.is_finite() { return PrepareResult::TypeMismatch }
+            }
+            Value::Boolean(_) if (*column).type_0 == COLUMN_TYPE_BOOLEAN => { }
+            Value::Blob(blob) if (*column).type_0 == COLUMN_TYPE_BLOB => {
+                if !column_fits(column, blob.len()) {
+                    return PrepareResult::StringTooLong
+                }
+            }
+            Value::Text(text) if (*column).type_0 == COLUMN_TYPE_TEXT => {
+                if !column_fits(column, text.len()) {
+                    return PrepareResult::StringTooLong
+                }
+            }
+            _ => { return PrepareResult::TypeMismatch }
+        }
+        i = i.wrapping_add(1)
+    }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn column_fits(mut column: *mut Column, mut length: usize)
+ -> bool {
+    /* Whether a text or blob of this length can go in the column */
+    if length > VALUE_MAX_SIZE { return 0 as libc::c_int != 0 }
+    return (*column).size == 0 as libc::c_int as libc::c_uint ||
+               length <= (*column).size as usize;
+}
+
+pub(crate) unsafe fn schema_column_index(mut schema: *mut Schema,
+                                  mut name: *const libc::c_char)
+ -> libc::c_int {
+    /* -1 when the table has no such column */
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < (*schema).columns.len() {
+        if strcmp((*(*schema).columns.as_ptr().offset(i as isize)).name.as_ptr() as *const libc::c_char,
+                  name) == 0 as libc::c_int {
+            return i as libc::c_int
+        }
+        i = i.wrapping_add(1)
+    }
+    return -(1 as libc::c_int);
+}
+
+pub(crate) unsafe fn schema_name(mut schema: *mut Schema) -> String {
+    return std::ffi::CStr::from_ptr((*schema).name.as_ptr() as
+                                        *const libc::c_char).to_string_lossy().into_owned();
+}
+
+pub(crate) fn new_schema() -> Schema {
+    return Schema{name: [0; TABLE_NAME_SIZE + 1],
+                  columns: Vec::new(),
+                  sql: String::new(),};
+}
+
+pub(crate) unsafe fn initialize_leaf_node(mut pager: *mut Pager,
+                                   mut node: *mut libc::c_void) {
+    set_node_type(node, NODE_LEAF);
+    set_node_root(node, 0 as libc::c_int != 0);
+    *leaf_node_num_cells(node) = 0 as libc::c_int as uint32_t;
+    *leaf_node_next_leaf(node) = 0 as libc::c_int as uint32_t;
+    *leaf_node_content_offset(node) = (*pager).usable_size;
+    // 0 represents no sibling
+}
+
+pub(crate) unsafe fn initialize_internal_node(mut node:
+                                                      *mut libc::c_void) {
+    set_node_type(node, NODE_INTERNAL);
+    set_node_root(node, 0 as libc::c_int != 0);
+    *internal_node_num_keys(node) = 0 as libc::c_int as uint32_t;
+}
+
+pub(crate) unsafe fn leaf_node_find(mut table: *mut Table,
+                                        mut page_num: uint32_t,
+                                        mut key: uint32_t)
+ -> Result<*mut Cursor, Error> {
+    let mut node: *mut libc::c_void = get_node((*table).pager, page_num)?;
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    let mut cursor: *mut Cursor =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               ::std::mem::size_of::<Cursor>() as libc::c_ulong) as
+            *mut Cursor;
+    (*cursor).table = table;
+    (*cursor).page_num = page_num;
+    (*cursor).end_of_table = 0 as libc::c_int != 0;
+    // Binary search
+    let mut min_index: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut one_past_max_index: uint32_t = num_cells;
+    while one_past_max_index != min_index {
+        let mut index: uint32_t =
+            min_index.wrapping_add(one_past_max_index).wrapping_div(2 as
+                                                                        libc::c_int
+                                                                        as
+                                                                        libc::c_uint);
+        let mut key_at_index: uint32_t = *leaf_node_key(node, index);
+        if key == key_at_index {
+            (*cursor).cell_num = index;
+            return Ok(cursor)
+        }
+        if key < key_at_index {
+            one_past_max_index = index
+        } else {
+            min_index = index.wrapping_add(1 as libc::c_int as libc::c_uint)
+        }
+    }
+    (*cursor).cell_num = min_index;
+    return Ok(cursor);
+}
+
+pub(crate) unsafe fn internal_node_find_child(mut node: *mut libc::c_void,
+                                                  mut key: uint32_t)
+ -> uint32_t {
+    /*
+  Return the index of the child which should contain
+  the given key.
+  */
+    let mut num_keys: uint32_t = *internal_node_num_keys(node);
+    /* Binary search */
+    let mut min_index: uint32_t =
+        0 as libc::c_int as uint32_t; /* there is one more child than key */
+    let mut max_index: uint32_t = num_keys;
+    while min_index != max_index {
+        let mut index: uint32_t =
+            min_index.wrapping_add(max_index).wrapping_div(2 as libc::c_int as
+                                                               libc::c_uint);
+        let mut key_to_right: uint32_t = *internal_node_key(node, index);
+        if key_to_right >= key {
+            max_index = index
+        } else {
+            min_index = index.wrapping_add(1 as libc::c_int as libc::c_uint)
+        }
+    }
+    return min_index;
+}
+
+pub(crate) unsafe fn internal_node_find(mut table: *mut Table,
+                                            mut page_num: uint32_t,
+                                            mut key: uint32_t)
+ -> Result<*mut Cursor, Error> {
+    let mut node: *mut libc::c_void = get_node((*table).pager, page_num)?;
+    let mut child_index: uint32_t = internal_node_find_child(node, key);
+    let mut child_num: uint32_t = *internal_node_child(node, child_index);
+    let mut child: *mut libc::c_void = get_node((*table).pager, child_num)?;
+    if get_node_type(child) == NODE_LEAF {
+        return leaf_node_find(table, child_num, key)
+    } else { return internal_node_find(table, child_num, key) };
+}
+/*
+Return the position of the given key.
+If the key is not present, return the position
+where it should be inserted
+*/
+
+pub(crate) unsafe fn table_find(mut table: *mut Table, mut key: uint32_t)
+ -> Result<*mut Cursor, Error> {
+    let mut root_page_num: uint32_t = (*table).root_page_num;
+    let mut root_node: *mut libc::c_void =
+        get_node((*table).pager, root_page_num)?;
+    if get_node_type(root_node) as libc::c_uint ==
+           NODE_LEAF as libc::c_int as libc::c_uint {
+        return leaf_node_find(table, root_page_num, key)
+    } else { return internal_node_find(table, root_page_num, key) };
+}
+
+pub(crate) unsafe fn table_start(mut table: *mut Table)
+ -> Result<*mut Cursor, Error> {
+    let mut cursor: *mut Cursor =
+        table_find(table, 0 as libc::c_int as uint32_t)?;
+    /* The leaf was just read by table_find, so it is still cached */
+    let mut node: *mut libc::c_void =
+        get_node((*table).pager, (*cursor).page_num)?;
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    (*cursor).end_of_table = num_cells == 0 as libc::c_int as libc::c_uint;
+    return Ok(cursor);
+}
+
+pub(crate) unsafe fn cursor_value(mut cursor: *mut Cursor)
+ -> Result<Vec<u8>, Error> {
+    /* The whole record, put back together from its overflow pages */
+    let mut pager: *mut Pager = (*(*cursor).table).pager;
+    let mut page_num: uint32_t = (*cursor).page_num;
+    let mut page: *mut libc::c_void = get_page(pager, page_num)?;
+    if !leaf_node_cell_ok(pager, page, (*cursor).cell_num) {
+        return Err(Error::Corrupt(page_num))
+    }
+    let mut payload_size: uint32_t =
+        *leaf_node_payload_size(page, (*cursor).cell_num);
+    let mut record: Vec<u8> =
+        ::std::slice::from_raw_parts(leaf_node_payload(pager, page,
+                                                       (*cursor).cell_num) as
+                                         *const u8,
+                                     payload_local_size(pager, payload_size) as
+                                         usize).to_vec();
+    if payload_spills(pager, payload_size) {
+        overflow_read(pager, page_num,
+                      *leaf_node_overflow(page, (*cursor).cell_num),
+                      payload_size, &mut record)?;
+    }
+    return Ok(record);
+}
+
+pub(crate) unsafe fn cursor_row(mut cursor: *mut Cursor, mut row: *mut Row)
+ -> Result<(), Error> {
+    let mut record: Vec<u8> = cursor_value(cursor)?;
+    if deserialize_row((*(*cursor).table).schema, &record, row).is_err() {
+        return Err(Error::Corrupt((*cursor).page_num))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn cursor_advance(mut cursor: *mut Cursor) -> Result<(), Error> {
+    let mut page_num: uint32_t = (*cursor).page_num;
+    let mut node: *mut libc::c_void =
+        get_node((*(*cursor).table).pager, page_num)?;
+    (*cursor).cell_num =
+        ((*cursor).cell_num as
+             libc::c_uint).wrapping_add(1 as libc::c_int as libc::c_uint) as
+            uint32_t as uint32_t;
+    if (*cursor).cell_num >= *leaf_node_num_cells(node) {
+        /* Advance to next leaf node */
+        let mut next_page_num: uint32_t = *leaf_node_next_leaf(node);
+        if next_page_num == 0 as libc::c_int as libc::c_uint {
+            /* This was rightmost leaf */
+            (*cursor).end_of_table = 1 as libc::c_int != 0
+        } else {
+            /* Only the root leaf of an empty table has no cells */
+            let mut next: *mut libc::c_void =
+                get_node((*(*cursor).table).pager, next_page_num)?;
+            if get_node_type(next) != NODE_LEAF ||
+                   *leaf_node_num_cells(next) ==
+                       0 as libc::c_int as libc::c_uint {
+                return Err(Error::Corrupt(next_page_num))
+            }
+            (*cursor).page_num = next_page_num;
+            (*cursor).cell_num = 0 as libc::c_int as uint32_t
+        }
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_open(mut filename: *const libc::c_char,
+                         mut page_size: uint32_t)
+ -> Result<*mut Pager, Error> {
+    /* page_size only counts for a new file, see pager_recover */
+    if !page_size_valid(page_size) { return Err(Error::PageSize(page_size)) }
+    let mut fd: libc::c_int =
+        open(filename, 0o2 as libc::c_int | 0o100 as libc::c_int,
+             0o200 as libc::c_int | 0o400 as libc::c_int);
+    if fd == -(1 as libc::c_int) { return Err(io_error("opening file")) }
+    let mut file_length: off_t =
+        lseek(fd, 0 as libc::c_int as __off_t, 2 as libc::c_int);
+    let mut pager: *mut Pager =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               ::std::mem::size_of::<Pager>() as libc::c_ulong) as *mut Pager;
+    (*pager).filename = strdup(filename);
+    (*pager).file_descriptor = fd;
+    (*pager).file_length = file_length;
+    pager_set_page_size(pager, page_size);
+    (*pager).pages_read = 0 as libc::c_int as uint64_t;
+    (*pager).pages_written = 0 as libc::c_int as uint64_t;
+    (*pager).changed = 0 as libc::c_int != 0;
+    (*pager).wal_filename =
+        sidecar_path(filename, b"-wal\x00" as *const u8 as *const libc::c_char);
+    (*pager).wal_fd = -(1 as libc::c_int);
+    (*pager).wal_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_checkpoint_seq = 0 as libc::c_int as uint32_t;
+    (*pager).wal_salt = [0 as libc::c_int as uint32_t; 2];
+    (*pager).wal_checksum = [0 as libc::c_int as uint32_t; 2];
+    (*pager).wal_committed_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_committed_checksum = [0 as libc::c_int as uint32_t; 2];
+    (*pager).synchronous = Synchronous::Full;
+    (*pager).torn_write_countdown = 0 as libc::c_int as uint32_t;
+    (*pager).crashed = 0 as libc::c_int != 0;
+    (*pager).in_transaction = 0 as libc::c_int != 0;
+    (*pager).savepoints = 0 as *mut Savepoint;
+    (*pager).savepoint_fd = -(1 as libc::c_int);
+    (*pager).savepoint_file_length = 0 as libc::c_int as off_t;
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < PAGER_HASH_BUCKETS {
+        (*pager).wal_index[i] = 0 as *mut PageIndexEntry;
+        i = i.wrapping_add(1)
+    }
+    (*pager).journal_mode = JournalMode::Wal;
+    (*pager).journal_filename =
+        sidecar_path(filename,
+                     b"-journal\x00" as *const u8 as *const libc::c_char);
+    (*pager).journal_fd = -(1 as libc::c_int);
+    (*pager).journal_records = 0 as libc::c_int as uint32_t;
+    (*pager).journal_db_size = 0 as libc::c_int as uint32_t;
+    (*pager).journal_synced = 1 as libc::c_int != 0;
+    i = 0 as libc::c_int as usize;
+    while i < PAGER_HASH_BUCKETS {
+        (*pager).journal_index[i] = 0 as *mut PageIndexEntry;
+        i = i.wrapping_add(1)
+    }
+    (*pager).num_pages = 0 as libc::c_int as uint32_t;
+    (*pager).cache_size = PAGER_DEFAULT_CACHE_SIZE;
+    (*pager).num_cached = 0 as libc::c_int as uint32_t;
+    (*pager).generation = 0 as libc::c_int as uint64_t;
+    i = 0 as libc::c_int as usize;
+    while i < PAGER_HASH_BUCKETS {
+        (*pager).buckets[i] = 0 as *mut CachedPage;
+        i = i.wrapping_add(1)
+    }
+    (*pager).lru_head = 0 as *mut CachedPage;
+    (*pager).lru_tail = 0 as *mut CachedPage;
+    (*pager).lock = LOCK_NONE;
+    (*pager).busy_timeout = PAGER_DEFAULT_BUSY_TIMEOUT;
+    (*pager).change_counter = 0 as libc::c_int as uint32_t;
+    (*pager).wal_db_size = 0 as libc::c_int as uint32_t;
+    (*pager).read_mark = -(1 as libc::c_int) as off_t;
+    (*pager).wal_backfilled = 0 as libc::c_int as uint32_t;
+    if let Err(error) = pager_recover(pager) {
+        pager_unlock(pager, LOCK_NONE);
+        pager_close(pager).ok();
+        return Err(error)
+    }
+    return Ok(pager);
+}
+
+pub(crate) unsafe fn pager_recover(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Leftovers of a crash are only replayed by the first connection
+  to open the file. Later ones leave the WAL in place and read
+  from it, and pager_refresh picks up a journal nobody owns.
+  The lock is kept for the caller to read the file with.
+  */
+    let mut waited: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut locked: bool =
+        pager_lock_byte(pager, libc::F_RDLCK as libc::c_short,
+                        LOCK_BYTE_CONNECTION, 1 as libc::c_int != 0,
+                        &mut waited);
+    /*
+  Recovery needs the page size before the file is trusted. The
+  header is checked again below; a file too short to have one
+  takes the size of a WAL or journal left by its creator, see
+  pager_adopt_page_size.
+  */
+    if (*pager).file_length >= FILE_HEADER_SIZE as off_t {
+        match check_file_header(pager) {
+            /* Its journal is not ours to replay either */
+            Err(Error::Version(version)) if version == LEGACY_FORMAT_VERSION
+            => {
+                return Err(Error::Version(version))
+            }
+            _ => { }
+        }
+    }
+    if locked && pager_only_connection(pager) {
+        locked = pager_lock(pager, LOCK_EXCLUSIVE, 1 as libc::c_int != 0);
+        if locked && pager_only_connection(pager) {
+            journal_restore(pager)?;
+            wal_recover(pager)?;
+        }
+    } else if locked {
+        locked = pager_lock(pager, LOCK_SHARED, 1 as libc::c_int != 0)
+    }
+    if !locked { return Err(Error::Locked) }
+    let mut changed: bool = 0 as libc::c_int != 0;
+    pager_refresh(pager, &mut changed)?;
+    let mut file_length: off_t = (*pager).file_length;
+    if file_length > 0 as libc::c_int as libc::c_long {
+        check_file_header(pager)?;
+    }
+    if file_length % (*pager).page_size as libc::c_long !=
+           0 as libc::c_int as libc::c_long {
+        /* The page cut short at the end */
+        return Err(Error::Corrupt((file_length / (*pager).page_size as off_t) as
+                                      uint32_t))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn db_open(mut filename: *const libc::c_char,
+                      mut page_size: uint32_t)
+ -> Result<*mut Table, Error> {
+    /* The table returned is the catalog, the others hang off it */
+    let mut pager: *mut Pager = pager_open(filename, page_size)?;
+    let mut table: *mut Table =
+        Box::into_raw(Box::new(Table{pager: pager,
+                                     root_page_num:
+                                         0 as libc::c_int as uint32_t,
+                                     schema:
+                                         schema_from_sql(CATALOG_SQL.as_bytes()),
+                                     tables: Vec::new(),}));
+    if let Err(error) = db_initialize(table) {
+        pager_abort(pager);
+        pager_close(pager).ok();
+        table_free(table);
+        return Err(error)
+    }
+    pager_unlock(pager, LOCK_NONE);
+    return Ok(table);
+}
+
+pub(crate) unsafe fn db_initialize(mut table: *mut Table) -> Result<(), Error> {
+    let mut pager: *mut Pager = (*table).pager;
+    if (*pager).num_pages == 0 as libc::c_int as libc::c_uint {
+        pager_begin_write(pager)?;
+    }
+    /* Another connection may have created it while this one waited */
+    if (*pager).num_pages == 0 as libc::c_int as libc::c_uint {
+        // New database file. Page 0 holds the file header,
+        // page 1 is the root of the empty catalog.
+        let mut header: *mut libc::c_void =
+            get_page(pager, 0 as libc::c_int as uint32_t)?;
+        pager_mark_dirty(pager, 0 as libc::c_int as uint32_t)?;
+        initialize_file_header(pager, header);
+        *file_header_root_page(header) = 1 as libc::c_int as uint32_t;
+        let mut root_node: *mut libc::c_void =
+            get_page(pager, 1 as libc::c_int as uint32_t)?;
+        pager_mark_dirty(pager, 1 as libc::c_int as uint32_t)?;
+        initialize_leaf_node(pager, root_node);
+        set_node_root(root_node, 1 as libc::c_int != 0);
+        pager_commit(pager)?;
+    }
+    return catalog_load(table);
+}
+
+pub(crate) unsafe fn catalog_load(mut catalog: *mut Table) -> Result<(), Error> {
+    /*
+  Catch up with the catalog as the file has it. Called with a
+  lock held before a statement is prepared and again before it
+  runs, so tables created by other connections, or taken away
+  again by a rollback, are seen. Tables whose entry is unchanged
+  are kept as they are, so pointers to them stay good.
+  */
+    let mut pager: *mut Pager = (*catalog).pager;
+    let mut header: *mut libc::c_void =
+        get_page(pager, 0 as libc::c_int as uint32_t)?;
+    (*catalog).root_page_num = *file_header_root_page(header);
+    let mut tables: Vec<*mut Table> = Vec::new();
+    let mut row: Row = Row{values: Vec::new(),};
+    let mut result: Result<(), Error> = Ok(());
+    let mut cursor: *mut Cursor = table_start(catalog)?;
+    while result.is_ok() && !(*cursor).end_of_table {
+        pager_release(pager);
+        result = cursor_row(cursor, &mut row);
+        if result.is_err() { break ; }
+        match catalog_entry(catalog, &mut row) {
+            Some(table) => { tables.push(table) }
+            None => {
+                result = Err(Error::Corrupt((*cursor).page_num));
+                break ;
+            }
+        }
+        result = cursor_advance(cursor)
+    }
+    free(cursor as *mut libc::c_void);
+    /* Free whichever list is not kept, minus the tables both share */
+    let mut old_tables: Vec<*mut Table> =
+        ::std::mem::take(&mut (*catalog).tables);
+    if result.is_err() {
+        ::std::mem::swap(&mut tables, &mut old_tables);
+    }
+    for table in old_tables.iter() {
+        if !tables.contains(table) { table_free(*table); }
+    }
+    (*catalog).tables = tables;
+    return result;
+}
+
+pub(crate) unsafe fn catalog_entry(mut catalog: *mut Table, mut row: *mut Row)
+ -> Option<*mut Table> {
+    /* The table a catalog row describes, parsed only when it is new;
+       None when the row is not one the catalog could have written */
+    let mut values: &Vec<Value> = &(*row).values;
+    let mut root_column: libc::c_int =
+        schema_column_index((*catalog).schema,
+                            b"root\x00" as *const u8 as *const libc::c_char);
+    let mut sql_column: libc::c_int =
+        schema_column_index((*catalog).schema,
+                            b"sql\x00" as *const u8 as *const libc::c_char);
+    let (root_page_num, sql) =
+        match (&values[root_column as usize], &values[sql_column as usize]) {
+            (Value::Integer(root_page_num), Value::Text(sql)) if
+            *root_page_num > 0 as libc::c_int as int64_t &&
+                *root_page_num <= 0xffffffff as libc::c_uint as int64_t => {
+                (*root_page_num as uint32_t, sql)
+            }
+            _ => { return None }
+        };
+    for table in (*catalog).tables.iter() {
+        if (**table).root_page_num == root_page_num &&
+               (*(**table).schema).sql == *sql {
+            /* .vacuum may have replaced the pager */
+            (**table).pager = (*catalog).pager;
+            return Some(*table)
+        }
+    }
+    let mut schema: *mut Schema = schema_from_sql(sql.as_bytes());
+    if schema.is_null() || root_page_num == 0 as libc::c_int as libc::c_uint
+       {
+        if !schema.is_null() { drop(Box::from_raw(schema)); }
+        return None
+    }
+    return Some(Box::into_raw(Box::new(Table{pager: (*catalog).pager,
+                                             root_page_num: root_page_num,
+                                             schema: schema,
+                                             tables: Vec::new(),})));
+}
+
+pub(crate) unsafe fn catalog_find(mut catalog: *mut Table,
+                           mut name: *const libc::c_char) -> *mut Table {
+    /* NULL when there is no such table */
+    if strcmp((*(*catalog).schema).name.as_ptr() as *const libc::c_char,
+              name) == 0 as libc::c_int {
+        return catalog
+    }
+    for table in (*catalog).tables.iter() {
+        if strcmp((*(**table).schema).name.as_ptr() as *const libc::c_char,
+                  name) == 0 as libc::c_int {
+            return *table
+        }
+    }
+    return 0 as *mut Table;
+}
+
+pub(crate) unsafe fn schema_from_sql(mut sql: &[u8]) -> *mut Schema {
+    /* Parsed the way CREATE TABLE was when the text was written, NULL if it does not */
+    let mut text: Vec<u8> = sql.to_vec();
+    text.push(0 as libc::c_int as u8);
+    let mut input_buffer: InputBuffer =
+        InputBuffer{buffer: text.as_mut_ptr() as *mut char,
+                    buffer_length: text.len(),
+                    input_length: sql.len() as ssize_t,};
+    let mut statement: Statement = new_statement();
+    if prepare_create_table(&mut input_buffer, &mut statement) as libc::c_uint
+           != PrepareResult::Success as libc::c_int as libc::c_uint {
+        return 0 as *mut Schema
+    }
+    return Box::into_raw(Box::new(statement.schema));
+}
+
+pub(crate) unsafe fn table_free(mut table: *mut Table) {
+    for child in (*table).tables.iter() { table_free(*child); }
+    if !(*table).schema.is_null() { drop(Box::from_raw((*table).schema)); }
+    drop(Box::from_raw(table));
+}
+
+pub(crate) unsafe fn pager_write_page(mut pager: *mut Pager, mut page_num: uint32_t,
+                               mut data: *const libc::c_void)
+ -> Result<(), Error> {
+    let mut offset: off_t =
+        lseek((*pager).file_descriptor,
+              (page_num as __off_t) * ((*pager).page_size as __off_t),
+              0 as libc::c_int);
+    if offset == -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("seeking"))
+    }
+    let mut bytes_written: ssize_t =
+        pager_write(pager, (*pager).file_descriptor, data,
+                    (*pager).page_size as size_t);
+    write_result(bytes_written, (*pager).page_size as size_t, "writing")?;
+    if offset + (*pager).page_size as off_t > (*pager).file_length {
+        (*pager).file_length = offset + (*pager).page_size as off_t
+    }
+    (*pager).pages_written = (*pager).pages_written.wrapping_add(1);
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_commit(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Append every dirty page to the WAL, flag the last frame as a
+  commit and sync the WAL. In rollback mode the pages go straight
+  to the database file once the journal is synced. Pages that
+  were only read are left alone.
+  */
+    if !(*pager).changed { return Ok(()) }
+    /* Let other readers of the file notice it was modified */
+    let mut header: *mut libc::c_void =
+        get_page(pager, 0 as libc::c_int as uint32_t)?;
+    *file_header_change_counter(header) =
+        (*file_header_change_counter(header)).wrapping_add(1 as libc::c_int as
+                                                               libc::c_uint);
+    pager_mark_dirty(pager, 0 as libc::c_int as uint32_t)?;
+    (*pager).change_counter = *file_header_change_counter(header);
+    (*pager).changed = 0 as libc::c_int != 0;
+    if (*pager).journal_mode == JournalMode::Rollback {
+        journal_sync(pager)?;
+        let mut cached: *mut CachedPage = (*pager).lru_head;
+        while !cached.is_null() {
+            if (*cached).dirty {
+                page_seal(pager, (*cached).data);
+                pager_write_page(pager, (*cached).page_num, (*cached).data)?;
+                (*cached).dirty = 0 as libc::c_int != 0
+            }
+            cached = (*cached).lru_next
+        }
+        /* Cut off pages spilled before a ROLLBACK TO dropped them */
+        let mut length: off_t =
+            ((*pager).num_pages as off_t) * ((*pager).page_size as off_t);
+        if (*pager).file_length > length {
+            if pager_truncate(pager, (*pager).file_descriptor, length) ==
+                   -(1 as libc::c_int) {
+                return Err(io_error("truncating db file"))
+            }
+            (*pager).file_length = length
+        }
+        if pager_fsync(pager, (*pager).file_descriptor, Synchronous::Normal,
+                       0 as libc::c_int != 0) == -(1 as libc::c_int) {
+            return Err(io_error("syncing db file"))
+        }
+        journal_finish(pager)?;
+        pager_sync_directory(pager, Synchronous::Full);
+        return Ok(())
+    }
+    let mut last: *mut CachedPage = 0 as *mut CachedPage;
+    let mut cached: *mut CachedPage = (*pager).lru_head;
+    while !cached.is_null() {
+        if (*cached).dirty {
+            if !last.is_null() {
+                wal_write_frame(pager, last, 0 as libc::c_int as uint32_t)?;
+            }
+            last = cached
+        }
+        cached = (*cached).lru_next
+    }
+    wal_write_frame(pager, last, (*pager).num_pages)?;
+    if pager_fsync(pager, (*pager).wal_fd, Synchronous::Full,
+                   1 as libc::c_int != 0) == -(1 as libc::c_int) {
+        return Err(io_error("syncing WAL"))
+    }
+    (*pager).wal_committed_frames = (*pager).wal_frames;
+    (*pager).wal_committed_checksum = (*pager).wal_checksum;
+    (*pager).wal_db_size = (*pager).num_pages;
+    /*
+  Neither worth waiting for nor worth failing a commit that is
+  already safe over, a later commit tries again.
+  */
+    if (*pager).wal_frames >= WAL_AUTOCHECKPOINT {
+        pager_checkpoint(pager, 0 as libc::c_int != 0).ok();
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_checkpoint(mut pager: *mut Pager, mut busy: bool)
+ -> Result<(), Error> {
+    /*
+  Copy committed pages from the WAL back into the database file,
+  and start the WAL over once nobody reads from it. Called by the
+  writer, so no commit is appended meanwhile.
+  Readers never wait for this. A reader finds pages missing from
+  its snapshot in the database file, so frames past the shortest
+  snapshot still being read stay in the WAL for a later
+  checkpoint. Fails with Error::Locked if a reader was taking its
+  snapshot and did not finish within the busy timeout.
+  */
+    if (*pager).wal_frames == 0 as libc::c_int as libc::c_uint {
+        return Ok(())
+    }
+    let mut waited: uint32_t = 0 as libc::c_int as uint32_t;
+    if !pager_lock_byte(pager, libc::F_WRLCK as libc::c_short,
+                        LOCK_BYTE_CHECKPOINT, busy, &mut waited) {
+        return Err(Error::Locked)
+    }
+    let mut result: Result<(), Error> =
+        wal_backfill(pager,
+                     pager_oldest_read_mark(pager,
+                                            (*pager).wal_committed_frames));
+    /* Readers of an empty snapshot only use the database file */
+    if result.is_ok() && (*pager).wal_backfilled == (*pager).wal_frames &&
+           lock_held((*pager).file_descriptor,
+                     LOCK_BYTE_READ_MARK + 1 as libc::c_int as off_t,
+                     0 as libc::c_int as off_t) ==
+               -(1 as libc::c_int) as off_t {
+        result = wal_reset(pager)
+    }
+    lock_byte((*pager).file_descriptor, libc::F_UNLCK as libc::c_short,
+              LOCK_BYTE_CHECKPOINT);
+    return result;
+}
+
+pub(crate) unsafe fn wal_backfill(mut pager: *mut Pager, mut frames: uint32_t)
+ -> Result<(), Error> {
+    /* Copy the first frames of the WAL that are not copied yet */
+    if frames <= (*pager).wal_backfilled { return Ok(()) }
+    /* Below FULL commits are not synced, make them safe before the copy */
+    if (*pager).synchronous < Synchronous::Full &&
+           pager_fsync(pager, (*pager).wal_fd, Synchronous::Normal,
+                       1 as libc::c_int != 0) == -(1 as libc::c_int) {
+        return Err(io_error("syncing WAL"))
+    }
+    /* Newest copy of each page as of the last frame to copy */
+    let mut result: Result<(), Error> = Ok(());
+    let mut index: [*mut PageIndexEntry; PAGER_HASH_BUCKETS] =
+        [0 as *mut PageIndexEntry; PAGER_HASH_BUCKETS];
+    let mut frame_header: [uint32_t; 6] = [0 as libc::c_int as uint32_t; 6];
+    let mut frame: uint32_t = (*pager).wal_backfilled;
+    while result.is_ok() && frame < frames {
+        result =
+            wal_read(pager, 0 as libc::c_int as uint32_t,
+                     wal_frame_offset(pager, frame), frame_header.as_mut_ptr() as *mut libc::c_void,
+                     WAL_FRAME_HEADER_SIZE);
+        if result.is_ok() {
+            page_index_set(index.as_mut_ptr(), frame_header[0], frame);
+        }
+        frame = frame.wrapping_add(1)
+    }
+    /* The last frame commits, and knows the database size back then */
+    let mut db_size: uint32_t = frame_header[1];
+    let mut page: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    let mut bucket: usize = 0 as libc::c_int as usize;
+    while result.is_ok() && bucket < PAGER_HASH_BUCKETS {
+        let mut entry: *mut PageIndexEntry = index[bucket];
+        while result.is_ok() && !entry.is_null() {
+            /* Pages dropped by ROLLBACK TO are not part of the database */
+            if (*entry).page_num < db_size {
+                result =
+                    wal_read(pager, (*entry).page_num,
+                             wal_frame_offset(pager, (*entry).slot) +
+                                 WAL_FRAME_HEADER_SIZE as off_t, page,
+                             (*pager).page_size);
+                if result.is_ok() {
+                    result = pager_write_page(pager, (*entry).page_num, page)
+                }
+            }
+            entry = (*entry).next
+        }
+        bucket = bucket.wrapping_add(1)
+    }
+    free(page);
+    page_index_clear(index.as_mut_ptr());
+    result?;
+    if pager_fsync(pager, (*pager).file_descriptor, Synchronous::Normal,
+                   0 as libc::c_int != 0) == -(1 as libc::c_int) {
+        return Err(io_error("syncing db file"))
+    }
+    (*pager).wal_backfilled = frames;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_sync(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Commit what is pending and fold the WAL into the database
+  file, which then stands on its own. The WAL stays while other
+  connections have the file open, they may still read from it.
+  */
+    pager_commit(pager)?;
+    match pager_begin_read(pager) {
+        Err(Error::Locked) => { return Ok(()) }
+        result => { result? }
+    }
+    if !pager_lock(pager, LOCK_EXCLUSIVE, 1 as libc::c_int != 0) ||
+           !pager_only_connection(pager) {
+        return Ok(())
+    }
+    pager_checkpoint(pager, 1 as libc::c_int != 0)?;
+    if (*pager).wal_fd != -(1 as libc::c_int) {
+        close((*pager).wal_fd);
+        (*pager).wal_fd = -(1 as libc::c_int);
+        pager_unlink(pager, (*pager).wal_filename);
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_close(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Drop the page cache without writing it back */
+    let mut result: Result<(), Error> = Ok(());
+    if close((*pager).file_descriptor) == -(1 as libc::c_int) {
+        result = Err(io_error("closing db file"))
+    }
+    if (*pager).wal_fd != -(1 as libc::c_int) { close((*pager).wal_fd); }
+    if (*pager).savepoint_fd != -(1 as libc::c_int) {
+        close((*pager).savepoint_fd);
+    }
+    page_index_clear((*pager).wal_index.as_mut_ptr());
+    /* An open journal stays behind and is rolled back on the next open */
+    if (*pager).journal_fd != -(1 as libc::c_int) {
+        close((*pager).journal_fd);
+    }
+    page_index_clear((*pager).journal_index.as_mut_ptr());
+    free((*pager).journal_filename as *mut libc::c_void);
+    pager_savepoints_pop(pager, 0 as *mut Savepoint);
+    let mut cached: *mut CachedPage = (*pager).lru_head;
+    while !cached.is_null() {
+        let mut next: *mut CachedPage = (*cached).lru_next;
+        free((*cached).data);
+        free(cached as *mut libc::c_void);
+        cached = next
+    }
+    free((*pager).wal_filename as *mut libc::c_void);
+    free((*pager).filename as *mut libc::c_void);
+    free(pager as *mut libc::c_void);
+    return result;
+}
+
+pub(crate) unsafe fn pager_stored_change_counter(mut pager: *mut Pager)
+ -> Result<uint32_t, Error> {
+    /* The header as committed, wherever its newest copy is */
+    let mut counter: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut wal_entry: *mut PageIndexEntry =
+        page_index_lookup((*pager).wal_index.as_mut_ptr(),
+                          0 as libc::c_int as uint32_t);
+    if !wal_entry.is_null() {
+        wal_read(pager, 0 as libc::c_int as uint32_t,
+                 wal_frame_offset(pager, (*wal_entry).slot) +
+                     WAL_FRAME_HEADER_SIZE as off_t +
+                     FILE_HEADER_CHANGE_COUNTER_OFFSET as off_t,
+                 &mut counter as *mut uint32_t as *mut libc::c_void,
+                 FILE_HEADER_CHANGE_COUNTER_SIZE)?;
+    } else if (*pager).file_length > 0 as libc::c_int as libc::c_long {
+        if lseek((*pager).file_descriptor,
+                 FILE_HEADER_CHANGE_COUNTER_OFFSET as __off_t,
+                 0 as libc::c_int) == -(1 as libc::c_int) as libc::c_long {
+            return Err(io_error("seeking"))
+        }
+        let mut bytes_read: ssize_t =
+            read((*pager).file_descriptor,
+                 &mut counter as *mut uint32_t as *mut libc::c_void,
+                 FILE_HEADER_CHANGE_COUNTER_SIZE as size_t);
+        if bytes_read == -(1 as libc::c_int) as libc::c_long {
+            return Err(io_error("reading file"))
+        }
+        /* A file too short for a header is refused when the header is read */
+        if bytes_read != FILE_HEADER_CHANGE_COUNTER_SIZE as libc::c_long {
+            counter = 0 as libc::c_int as uint32_t
+        }
+    }
+    return Ok(counter);
+}
+
+pub(crate) unsafe fn pager_refresh(mut pager: *mut Pager, mut changed: *mut bool)
+ -> Result<(), Error> {
+    /*
+  Catch up with other connections after taking a lock. A journal
+  found while nobody else can be writing belongs to a process
+  that died mid-transaction and is rolled back first. Cached
+  pages are dropped if the file changed since this connection
+  last looked at it. The committed WAL frames seen here are the
+  snapshot read until the lock is dropped, published by a read
+  mark. Fails with Error::Locked if the journal could not be
+  locked for rollback or a checkpoint did not finish in time.
+  */
+    *changed = 0 as libc::c_int != 0;
+    if (*pager).journal_fd == -(1 as libc::c_int) &&
+           access((*pager).journal_filename, 0 as libc::c_int) ==
+               0 as libc::c_int {
+        if !pager_lock(pager, LOCK_EXCLUSIVE, 1 as libc::c_int != 0) {
+            return Err(Error::Locked)
+        }
+        (*pager).file_length =
+            lseek((*pager).file_descriptor, 0 as libc::c_int as __off_t,
+                  2 as libc::c_int);
+        journal_restore(pager)?;
+        *changed = 1 as libc::c_int != 0
+    }
+    /* Keep checkpoints from copying frames while the snapshot is taken */
+    let mut waited: uint32_t = 0 as libc::c_int as uint32_t;
+    if !pager_lock_byte(pager, libc::F_RDLCK as libc::c_short,
+                        LOCK_BYTE_CHECKPOINT, 1 as libc::c_int != 0,
+                        &mut waited) {
+        return Err(Error::Locked)
+    }
+    (*pager).file_length =
+        lseek((*pager).file_descriptor, 0 as libc::c_int as __off_t,
+              2 as libc::c_int);
+    let mut stored: Result<uint32_t, Error> =
+        match wal_refresh(pager) {
+            Ok(refreshed) => {
+                if refreshed { *changed = 1 as libc::c_int != 0 }
+                pager_set_read_mark(pager, (*pager).wal_committed_frames);
+                pager_stored_change_counter(pager)
+            }
+            Err(error) => Err(error),
+        };
+    lock_byte((*pager).file_descriptor, libc::F_UNLCK as libc::c_short,
+              LOCK_BYTE_CHECKPOINT);
+    let mut counter: uint32_t = stored?;
+    let mut num_pages: uint32_t =
+        ((*pager).file_length / (*pager).page_size as off_t) as uint32_t;
+    if (*pager).wal_committed_frames > 0 as libc::c_int as libc::c_uint {
+        num_pages = (*pager).wal_db_size
+    }
+    if num_pages != (*pager).num_pages || counter != (*pager).change_counter
+       {
+        *changed = 1 as libc::c_int != 0
+    }
+    (*pager).change_counter = counter;
+    if *changed {
+        pager_drop_pages(pager, 0 as libc::c_int as uint32_t);
+        /* Nothing is written yet, so savepoints start from here */
+        let mut savepoint: *mut Savepoint = (*pager).savepoints;
+        while !savepoint.is_null() {
+            (*savepoint).num_pages = num_pages;
+            savepoint = (*savepoint).prev
+        }
+    }
+    (*pager).num_pages = num_pages;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_begin_read(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Called before reading the database, a no-op inside a transaction */
+    if (*pager).lock != LOCK_NONE { return Ok(()) }
+    let mut changed: bool = 0 as libc::c_int != 0;
+    if !pager_lock(pager, LOCK_SHARED, 1 as libc::c_int != 0) {
+        return Err(Error::Locked)
+    }
+    if let Err(error) = pager_refresh(pager, &mut changed) {
+        pager_unlock(pager, LOCK_NONE);
+        return Err(error)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_begin_write(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Called before a statement that may change the database. One
+  connection writes at a time. In rollback mode pages go to the
+  database file itself, so readers have to be gone as well.
+  */
+    if (*pager).lock >= LOCK_RESERVED { return Ok(()) }
+    let mut changed: bool = 0 as libc::c_int != 0;
+    /* Nothing is read yet, wait in line without blocking the writer */
+    if !(*pager).in_transaction { pager_unlock(pager, LOCK_NONE); }
+    if !pager_lock(pager, LOCK_RESERVED, 1 as libc::c_int != 0) {
+        return Err(Error::Locked)
+    }
+    /* Another connection may have committed since the last look */
+    let mut result: Result<(), Error> = pager_refresh(pager, &mut changed);
+    if result.is_ok() && changed && (*pager).in_transaction {
+        /* What the transaction read so far is out of date */
+        result = Err(Error::Locked)
+    }
+    if result.is_ok() && (*pager).journal_mode == JournalMode::Rollback {
+        if !pager_lock(pager, LOCK_EXCLUSIVE, 1 as libc::c_int != 0) {
+            result = Err(Error::Locked)
+        } else {
+            /* Frames left by a connection in WAL mode come first */
+            match pager_checkpoint(pager, 1 as libc::c_int != 0) {
+                Err(Error::Locked) => { }
+                checkpoint => { result = checkpoint }
+            }
+        }
+    }
+    if result.is_err() { pager_unlock(pager, LOCK_SHARED); }
+    return result;
+}
+
+pub(crate) unsafe fn pager_statement_end(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Autocommit outside of BEGIN ... COMMIT and let others in */
+    if (*pager).in_transaction { return Ok(()) }
+    pager_commit(pager)?;
+    pager_unlock(pager, LOCK_NONE);
+    return Ok(());
+}
+
+pub(crate) unsafe fn savepoint_lookup(mut savepoint: *mut Savepoint,
+                               mut page_num: uint32_t) -> *mut PageSnapshot {
+    let mut snapshot: *mut PageSnapshot =
+        (*savepoint).snapshots[(page_num as
+                                    usize).wrapping_rem(PAGER_HASH_BUCKETS)];
+    while !snapshot.is_null() && (*snapshot).page_num != page_num {
+        snapshot = (*snapshot).next
+    }
+    return snapshot;
+}
+
+pub(crate) unsafe fn savepoint_file_append(mut pager: *mut Pager,
+                                           mut data: *const libc::c_void)
+ -> Result<off_t, Error> {
+    /*
+  Snapshots go to a file rather than memory, so a transaction
+  that changes many pages stays within cache_size. The file is
+  unlinked as soon as it is made and never needs to be synced.
+  */
+    if (*pager).savepoint_fd == -(1 as libc::c_int) {
+        let mut path: *mut libc::c_char =
+            sidecar_path((*pager).filename,
+                         b"-savepoint-XXXXXX\x00" as *const u8 as
+                             *const libc::c_char);
+        let mut fd: libc::c_int = mkstemp(path);
+        if fd == -(1 as libc::c_int) {
+            let mut error: Error = io_error("creating savepoint file");
+            free(path as *mut libc::c_void);
+            return Err(error)
+        }
+        unlink(path);
+        free(path as *mut libc::c_void);
+        (*pager).savepoint_fd = fd
+    }
+    let mut offset: off_t = (*pager).savepoint_file_length;
+    let mut bytes_written: ssize_t =
+        pwrite((*pager).savepoint_fd, data, (*pager).page_size as size_t,
+               offset);
+    write_result(bytes_written, (*pager).page_size as size_t,
+                 "writing savepoint file")?;
+    (*pager).savepoint_file_length = offset + (*pager).page_size as off_t;
+    return Ok(offset);
+}
+
+pub(crate) unsafe fn savepoint_file_read(mut pager: *mut Pager,
+                                         mut snapshot: *mut PageSnapshot,
+                                         mut page: *mut libc::c_void)
+ -> Result<(), Error> {
+    let mut bytes_read: ssize_t =
+        pread((*pager).savepoint_fd, page, (*pager).page_size as size_t,
+              (*snapshot).offset);
+    if bytes_read == -(1 as libc::c_int) as libc::c_long {
+        return Err(io_error("reading savepoint file"))
+    }
+    if bytes_read != (*pager).page_size as libc::c_long {
+        return Err(Error::Io("reading savepoint file", libc::EIO))
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn savepoint_add(mut savepoint: *mut Savepoint,
+                            mut page_num: uint32_t, mut offset: off_t) {
+    let mut bucket: usize =
+        (page_num as usize).wrapping_rem(PAGER_HASH_BUCKETS);
+    let mut snapshot: *mut PageSnapshot =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               ::std::mem::size_of::<PageSnapshot>() as libc::c_ulong) as
+            *mut PageSnapshot;
+    (*snapshot).page_num = page_num;
+    (*snapshot).offset = offset;
+    (*snapshot).next = (*savepoint).snapshots[bucket];
+    (*savepoint).snapshots[bucket] = snapshot;
+}
+
+pub(crate) unsafe fn savepoint_free(mut savepoint: *mut Savepoint) {
+    let mut bucket: usize = 0 as libc::c_int as usize;
+    while bucket < PAGER_HASH_BUCKETS {
+        let mut snapshot: *mut PageSnapshot = (*savepoint).snapshots[bucket];
+        while !snapshot.is_null() {
+            let mut next: *mut PageSnapshot = (*snapshot).next;
+            free(snapshot as *mut libc::c_void);
+            snapshot = next
+        }
+        bucket = bucket.wrapping_add(1)
+    }
+    free(savepoint as *mut libc::c_void);
+}
+
+pub(crate) unsafe fn pager_snapshot(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Keep the content a page had when each open savepoint was
+  taken, the first time it changes afterwards. Callers may
+  already have modified the cached copy, so it is read from
+  storage. Storage cannot hold anything newer: only dirty pages
+  are spilled, and pages dirty when a savepoint is taken are
+  copied right away by pager_savepoint_push.
+  */
+    let mut offset: off_t = -(1 as libc::c_int) as off_t;
+    let mut savepoint: *mut Savepoint = (*pager).savepoints;
+    while !savepoint.is_null() {
+        /* Pages appended after the savepoint are dropped instead */
+        if page_num < (*savepoint).num_pages &&
+               savepoint_lookup(savepoint, page_num).is_null() {
+            /* One copy in the file serves every savepoint missing it */
+            if offset == -(1 as libc::c_int) as libc::c_long {
+                let mut data: *mut libc::c_void =
+                    calloc(1 as libc::c_int as libc::c_ulong,
+                           (*pager).page_size as libc::c_ulong);
+                let mut appended: Result<off_t, Error> =
+                    match pager_read_page(pager, page_num, data) {
+                        Ok(()) => savepoint_file_append(pager, data),
+                        Err(error) => Err(error),
+                    };
+                free(data);
+                offset = appended?
+            }
+            savepoint_add(savepoint, page_num, offset);
+        }
+        savepoint = (*savepoint).prev
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_savepoint_push(mut pager: *mut Pager,
+                                   mut name: *const libc::c_char)
+ -> Result<(), Error> {
+    let mut savepoint: *mut Savepoint =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               ::std::mem::size_of::<Savepoint>() as libc::c_ulong) as
+            *mut Savepoint;
+    strcpy((*savepoint).name.as_mut_ptr(), name);
+    (*savepoint).num_pages = (*pager).num_pages;
+    let mut bucket: usize = 0 as libc::c_int as usize;
+    while bucket < PAGER_HASH_BUCKETS {
+        (*savepoint).snapshots[bucket] = 0 as *mut PageSnapshot;
+        bucket = bucket.wrapping_add(1)
+    }
+    /* Storage is behind for these, so take them from the cache now */
+    let mut cached: *mut CachedPage = (*pager).lru_head;
+    while !cached.is_null() {
+        if (*cached).dirty {
+            match savepoint_file_append(pager, (*cached).data) {
+                Ok(offset) => {
+                    savepoint_add(savepoint, (*cached).page_num, offset)
+                }
+                Err(error) => { savepoint_free(savepoint); return Err(error) }
+            }
+        }
+        cached = (*cached).lru_next
+    }
+    (*savepoint).prev = (*pager).savepoints;
+    (*pager).savepoints = savepoint;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_savepoints_pop(mut pager: *mut Pager,
+                                   mut until: *mut Savepoint) {
+    /* Discard savepoints above `until`, or all of them when it is NULL */
+    while (*pager).savepoints != until {
+        let mut savepoint: *mut Savepoint = (*pager).savepoints;
+        (*pager).savepoints = (*savepoint).prev;
+        savepoint_free(savepoint);
+    }
+    /* The transaction is over, its snapshots can be written over */
+    if (*pager).savepoints.is_null() &&
+           (*pager).savepoint_fd != -(1 as libc::c_int) {
+        ftruncate((*pager).savepoint_fd, 0 as libc::c_int as __off_t);
+        (*pager).savepoint_file_length = 0 as libc::c_int as off_t
+    };
+}
+
+pub(crate) unsafe fn pager_savepoint_find(mut pager: *mut Pager,
+                                   mut name: *const libc::c_char)
+ -> *mut Savepoint {
+    /* The innermost savepoint wins when a name is reused */
+    let mut savepoint: *mut Savepoint = (*pager).savepoints;
+    while !savepoint.is_null() {
+        if strcmp((*savepoint).name.as_mut_ptr(), name) == 0 as libc::c_int {
+            return savepoint
+        }
+        savepoint = (*savepoint).prev
+    }
+    return 0 as *mut Savepoint;
+}
+
+pub(crate) unsafe fn pager_begin(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Nothing is pending between statements, so storage is committed */
+    pager_savepoint_push(pager, b"\x00" as *const u8 as *const libc::c_char)?;
+    (*pager).in_transaction = 1 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_savepoint(mut pager: *mut Pager,
+                              mut name: *const libc::c_char)
+ -> Result<(), Error> {
+    /* SAVEPOINT outside of BEGIN starts a transaction RELEASE ends */
+    pager_savepoint_push(pager, name)?;
+    (*pager).in_transaction = 1 as libc::c_int != 0;
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_end(mut pager: *mut Pager) -> Result<(), Error> {
+    /* COMMIT */
+    (*pager).in_transaction = 0 as libc::c_int != 0;
+    pager_commit(pager)?;
+    pager_savepoints_pop(pager, 0 as *mut Savepoint);
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_savepoint_release(mut pager: *mut Pager,
+                                      mut savepoint: *mut Savepoint)
+ -> Result<(), Error> {
+    /*
+  RELEASE keeps the changes. Enclosing savepoints still hold
+  what they need to undo them, so inner ones are just dropped.
+  */
+    let mut prev: *mut Savepoint = (*savepoint).prev;
+    if prev.is_null() { return pager_end(pager) }
+    pager_savepoints_pop(pager, prev);
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_drop_pages(mut pager: *mut Pager, mut num_pages: uint32_t) {
+    /*
+  Forget cached pages from num_pages on and shrink the database
+  to that size. Copies already spilled to storage lie past the
+  end, where pager_read_page ignores them until commit or
+  checkpoint trims them.
+  */
+    let mut cached: *mut CachedPage = (*pager).lru_head;
+    while !cached.is_null() {
+        let mut next: *mut CachedPage = (*cached).lru_next;
+        if (*cached).page_num >= num_pages {
+            pager_lru_unlink(pager, cached);
+            pager_hash_remove(pager, cached);
+            (*pager).num_cached = (*pager).num_cached.wrapping_sub(1);
+            free((*cached).data);
+            free(cached as *mut libc::c_void);
+        }
+        cached = next
+    }
+    (*pager).num_pages = num_pages;
+}
+
+pub(crate) unsafe fn pager_rollback_to(mut pager: *mut Pager,
+                                mut savepoint: *mut Savepoint)
+ -> Result<(), Error> {
+    /*
+  ROLLBACK TO puts every page back the way it was when the
+  savepoint was taken, including pages rewritten by splits and
+  new roots, and drops pages appended since. Storage is left
+  alone: the restored pages are dirty and overwrite it at commit.
+  The savepoint stays open.
+  */
+    pager_savepoints_pop(pager, savepoint);
+    pager_drop_pages(pager, (*savepoint).num_pages);
+    let mut bucket: usize = 0 as libc::c_int as usize;
+    while bucket < PAGER_HASH_BUCKETS {
+        let mut snapshot: *mut PageSnapshot = (*savepoint).snapshots[bucket];
+        while !snapshot.is_null() {
+            pager_release(pager);
+            let mut page: *mut libc::c_void =
+                get_page(pager, (*snapshot).page_num)?;
+            savepoint_file_read(pager, snapshot, page)?;
+            /* Not pager_mark_dirty, the snapshots are already taken */
+            (*pager_lookup(pager, (*snapshot).page_num)).dirty =
+                1 as libc::c_int != 0;
+            (*pager).changed = 1 as libc::c_int != 0;
+            snapshot = (*snapshot).next
+        }
+        bucket = bucket.wrapping_add(1)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn wal_rollback(mut pager: *mut Pager) -> Result<(), Error> {
+    /* Forget the frames spilled since the last commit */
+    if (*pager).wal_frames == (*pager).wal_committed_frames { return Ok(()) }
+    (*pager).wal_frames = (*pager).wal_committed_frames;
+    (*pager).wal_checksum = (*pager).wal_committed_checksum;
+    let mut length: off_t = 0 as libc::c_int as off_t;
+    if (*pager).wal_frames > 0 as libc::c_int as libc::c_uint {
+        length = wal_frame_offset(pager, (*pager).wal_frames)
+    }
+    if pager_truncate(pager, (*pager).wal_fd, length) == -(1 as libc::c_int)
+       {
+        return Err(io_error("truncating WAL"))
+    }
+    page_index_clear((*pager).wal_index.as_mut_ptr());
+    let mut frame_header: [uint32_t; 6] = [0 as libc::c_int as uint32_t; 6];
+    let mut frame: uint32_t = 0 as libc::c_int as uint32_t;
+    while frame < (*pager).wal_frames {
+        wal_read(pager, 0 as libc::c_int as uint32_t,
+                 wal_frame_offset(pager, frame), frame_header.as_mut_ptr() as *mut libc::c_void,
+                 WAL_FRAME_HEADER_SIZE)?;
+        page_index_set((*pager).wal_index.as_mut_ptr(), frame_header[0],
+                       frame);
+        frame = frame.wrapping_add(1)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_rollback(mut pager: *mut Pager) -> Result<(), Error> {
+    /*
+  Undo everything since BEGIN. Pages spilled to storage are
+  taken back first, then cached pages are reset from their
+  snapshots and pages the transaction appended are dropped.
+  */
+    if (*pager).journal_mode == JournalMode::Rollback {
+        if (*pager).journal_fd != -(1 as libc::c_int) {
+            close((*pager).journal_fd);
+            (*pager).journal_fd = -(1 as libc::c_int);
+            page_index_clear((*pager).journal_index.as_mut_ptr());
+            journal_restore(pager)?;
+        }
+    } else { wal_rollback(pager)?; }
+    let mut savepoint: *mut Savepoint = (*pager).savepoints;
+    while !(*savepoint).prev.is_null() { savepoint = (*savepoint).prev }
+    pager_drop_pages(pager, (*savepoint).num_pages);
+    let mut cached: *mut CachedPage = (*pager).lru_head;
+    while !cached.is_null() {
+        let mut snapshot: *mut PageSnapshot =
+            savepoint_lookup(savepoint, (*cached).page_num);
+        if !snapshot.is_null() {
+            savepoint_file_read(pager, snapshot, (*cached).data)?;
+            (*cached).dirty = 0 as libc::c_int != 0
+        }
+        cached = (*cached).lru_next
+    }
+    (*pager).changed = 0 as libc::c_int != 0;
+    (*pager).in_transaction = 0 as libc::c_int != 0;
+    pager_savepoints_pop(pager, 0 as *mut Savepoint);
+    return Ok(());
+}
+
+pub(crate) unsafe fn pager_abort(mut pager: *mut Pager) {
+    /*
+  Give up on a statement that failed half way, and on the
+  transaction around it, without touching storage again. A
+  journal stays behind for pager_refresh to roll back, frames
+  past the last commit are forgotten with the rest of the WAL
+  index, which is read again from the start. Nothing cached can
+  be trusted any more.
+  */
+    if (*pager).journal_fd != -(1 as libc::c_int) {
+        close((*pager).journal_fd);
+        (*pager).journal_fd = -(1 as libc::c_int);
+        page_index_clear((*pager).journal_index.as_mut_ptr());
+    }
+    page_index_clear((*pager).wal_index.as_mut_ptr());
+    (*pager).wal_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_committed_frames = 0 as libc::c_int as uint32_t;
+    (*pager).wal_db_size = 0 as libc::c_int as uint32_t;
+    (*pager).wal_backfilled = 0 as libc::c_int as uint32_t;
+    pager_drop_pages(pager, 0 as libc::c_int as uint32_t);
+    (*pager).changed = 0 as libc::c_int != 0;
+    (*pager).in_transaction = 0 as libc::c_int != 0;
+    pager_savepoints_pop(pager, 0 as *mut Savepoint);
+    pager_unlock(pager, LOCK_NONE);
+}
+
+pub(crate) unsafe fn pager_statement_finish<T>(mut pager: *mut Pager,
+                                        mut result: Result<T, Error>)
+ -> Result<T, Error> {
+    /*
+  End a statement the way its result calls for. Errors found
+  before anything was changed leave the transaction as it is,
+  any other failure aborts it.
+  */
+    match result {
+        Ok(_) | Err(Error::DuplicateKey) | Err(Error::Syntax(_)) |
+        Err(Error::Locked) | Err(Error::TransactionActive) |
+        Err(Error::UnrecognizedCommand) => {
+            if let Err(error) = pager_statement_end(pager) {
+                pager_abort(pager);
+                return Err(error)
+            }
+        }
+        Err(_) => { pager_abort(pager); }
+    }
+    return result;
+}
+
+pub(crate) unsafe fn pager_set_journal_mode(mut pager: *mut Pager,
+                                    mut journal_mode: JournalMode)
+ -> Result<(), Error> {
+    if journal_mode == (*pager).journal_mode { return Ok(()) }
+    /* Leave nothing behind in the sidecar of the old mode */
+    pager_sync(pager)?;
+    (*pager).journal_mode = journal_mode;
+    return Ok(());
+}
+
+pub(crate) fn journal_mode_name(mut journal_mode: JournalMode)
+ -> &'static str {
+    match journal_mode {
+        JournalMode::Wal => { return "wal" }
+        JournalMode::Rollback => { return "rollback" }
+    }
+}
+
+impl ::std::str::FromStr for JournalMode {
+    type Err = UnknownSetting;
+    fn from_str(name: &str) -> Result<JournalMode, UnknownSetting> {
+        /* The names the pragma and --journal-mode take */
+        match name {
+            "wal" => { return Ok(JournalMode::Wal) }
+            "rollback" => { return Ok(JournalMode::Rollback) }
+            _ => { return Err(UnknownSetting(name.to_string())) }
+        }
+    }
+}
+
+pub(crate) fn synchronous_name(mut synchronous: Synchronous)
+ -> &'static str {
+    match synchronous {
+        Synchronous::Off => { return "off" }
+        Synchronous::Normal => { return "normal" }
+        Synchronous::Full => { return "full" }
+    }
+}
+
+impl ::std::str::FromStr for Synchronous {
+    type Err = UnknownSetting;
+    fn from_str(name: &str) -> Result<Synchronous, UnknownSetting> {
+        match name {
+            "off" => { return Ok(Synchronous::Off) }
+            "normal" => { return Ok(Synchronous::Normal) }
+            "full" => { return Ok(Synchronous::Full) }
+            _ => { return Err(UnknownSetting(name.to_string())) }
+        }
+    }
+}
+
+pub(crate) unsafe fn db_close(mut table: *mut Table) -> Result<(), Error> {
+    let mut pager: *mut Pager = (*table).pager;
+    let mut result: Result<(), Error> = Ok(());
+    /* A transaction that was never committed does not survive */
+    if (*pager).in_transaction { result = pager_rollback(pager) }
+    if result.is_ok() { result = pager_sync(pager) }
+    /* Closed either way, what is left over is recovered on the next open */
+    let mut closed: Result<(), Error> = pager_close(pager);
+    table_free(table);
+    return result.and(closed);
+}
+
+pub(crate) unsafe fn db_vacuum(mut table: *mut Table) -> Result<(), Error> {
+    /*
+  Rebuild every tree into a fresh file next to the database, the
+  catalog first and then the tables in catalog order. Each tree
+  starts with its root, followed by its leaves packed in key
+  order on consecutive pages and its internal levels.
+  The rebuilt file is synced before it is renamed over the
+  original, so a crash leaves either the old or the new file.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut filename: *mut libc::c_char = (*pager).filename;
+    let mut vacuum_filename: *mut libc::c_char =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               strlen(filename).wrapping_add(8 as libc::c_int as
+                                                 libc::c_ulong)) as
+            *mut libc::c_char;
+    strcpy(vacuum_filename, filename);
+    strcat(vacuum_filename, b"-vacuum\x00" as *const u8 as *const libc::c_char);
+    /* Leftovers of an interrupted vacuum */
+    unlink(vacuum_filename);
+    let mut vacuum_wal_filename: *mut libc::c_char =
+        sidecar_path(vacuum_filename,
+                     b"-wal\x00" as *const u8 as *const libc::c_char);
+    unlink(vacuum_wal_filename);
+    free(vacuum_wal_filename as *mut libc::c_void);
+    /* The WAL belongs to the file about to be replaced */
+    let mut result: Result<(), Error> = pager_sync(pager);
+    if result.is_ok() {
+        match pager_open(vacuum_filename, (*pager).page_size) {
+            Ok(new_pager) => {
+                (*new_pager).synchronous = (*pager).synchronous;
+                result = vacuum_build(table, new_pager);
+                if result.is_ok() { result = pager_sync(new_pager) }
+                let mut closed: Result<(), Error> = pager_close(new_pager);
+                result = result.and(closed)
+            }
+            Err(error) => { result = Err(error) }
+        }
+    }
+    if result.is_ok() &&
+           rename(vacuum_filename, filename) == -(1 as libc::c_int) {
+        result = Err(io_error("replacing db file"))
+    }
+    if result.is_err() { unlink(vacuum_filename); }
+    free(vacuum_filename as *mut libc::c_void);
+    result?;
+    pager_sync_directory(pager, Synchronous::Full);
+    /*
+  Everything cached belongs to the replaced file. The old file
+  stays open until the new one is, so a failure leaves a table
+  that still works.
+  */
+    let mut new_pager: *mut Pager =
+        pager_open(filename, (*pager).page_size)?;
+    (*new_pager).journal_mode = (*pager).journal_mode;
+    (*new_pager).synchronous = (*pager).synchronous;
+    (*table).pager = new_pager;
+    pager_close(pager)?;
+    /* The tables moved to new root pages */
+    return catalog_load(table);
+}
+
+pub(crate) unsafe fn vacuum_build(mut catalog: *mut Table,
+                           mut new_pager: *mut Pager) -> Result<(), Error> {
+    let mut pager: *mut Pager = (*catalog).pager;
+    let mut header: *mut libc::c_void =
+        get_page(new_pager, 0 as libc::c_int as uint32_t)?;
+    pager_mark_dirty(new_pager, 0 as libc::c_int as uint32_t)?;
+    initialize_file_header(new_pager, header);
+    /* The rebuilt file replaces this one, keep counting from it */
+    *file_header_change_counter(header) =
+        *file_header_change_counter(get_page(pager,
+                                             0 as libc::c_int as uint32_t)?);
+    let mut roots: Vec<uint32_t> = Vec::new();
+    for table in (*catalog).tables.iter() {
+        roots.push(vacuum_build_tree(*table, new_pager,
+                                     0 as *mut Vec<uint32_t>)?);
+    }
+    /* The catalog rows are copied with the new root pages in them */
+    let mut catalog_root: uint32_t =
+        vacuum_build_tree(catalog, new_pager, &mut roots)?;
+    header = get_page(new_pager, 0 as libc::c_int as uint32_t)?;
+    pager_mark_dirty(new_pager, 0 as libc::c_int as uint32_t)?;
+    *file_header_root_page(header) = catalog_root;
+    return Ok(());
+}
+
+pub(crate) unsafe fn vacuum_build_tree(mut table: *mut Table,
+                                mut new_pager: *mut Pager,
+                                mut roots: *mut Vec<uint32_t>)
+ -> Result<uint32_t, Error> {
+    /*
+  Copy one tree to pages added at the end of new_pager: the
+  root, the leaves, each followed by the overflow pages of its
+  rows, then the internal levels. Returns the root. The catalog
+  passes the new roots of its tables in catalog order, to be
+  stored in its rows in place of the old ones.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut root_page_num: uint32_t = get_unused_page_num(new_pager)?;
+    get_page(new_pager, root_page_num)?;
+    pager_mark_dirty(new_pager, root_page_num)?;
+    /* Add up the cells so they can be spread evenly over the leaves */
+    let mut total_size: uint64_t = 0 as libc::c_int as uint64_t;
+    let mut result: Result<(), Error> = Ok(());
+    let mut cursor: *mut Cursor = table_start(table)?;
+    while result.is_ok() && !(*cursor).end_of_table {
+        pager_release(pager);
+        match get_page(pager, (*cursor).page_num) {
+            Ok(node) => {
+                total_size =
+                    total_size.wrapping_add(leaf_node_cell_size(pager, node,
+                                                                (*cursor).cell_num).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE)
+                                                as uint64_t)
+            }
+            Err(error) => { result = Err(error); break ; }
+        }
+        result = cursor_advance(cursor)
+    }
+    free(cursor as *mut libc::c_void);
+    result?;
+    let mut node_pages: Vec<uint32_t> = Vec::new();
+    let mut node_max_keys: Vec<uint32_t> = Vec::new();
+    cursor = table_start(table)?;
+    result =
+        vacuum_fill_leaves(cursor, new_pager, roots, root_page_num,
+                           total_size, &mut node_pages, &mut node_max_keys);
+    free(cursor as *mut libc::c_void);
+    result?;
+    if node_pages.is_empty() {
+        /* An empty table is a single empty leaf */
+        initialize_leaf_node(pager, get_page(new_pager, root_page_num)?);
+        pager_mark_dirty(new_pager, root_page_num)?;
+        node_pages.push(root_page_num);
+        node_max_keys.push(0 as libc::c_int as uint32_t);
+    }
+    let mut num_nodes: uint32_t = node_pages.len() as uint32_t;
+    /* Build internal levels bottom-up until a single node is left */
+    while num_nodes > 1 as libc::c_int as libc::c_uint {
+        let mut max_children: uint32_t =
+            (*new_pager).internal_node_max_cells.wrapping_add(1 as libc::c_int
+                                                                  as
+                                                                  libc::c_uint);
+        let mut num_parents: uint32_t =
+            num_nodes.wrapping_add(max_children).wrapping_sub(1 as libc::c_int
+                                                                  as
+                                                                  libc::c_uint).wrapping_div(max_children);
+        let mut parent_pages: Vec<uint32_t> = vec![0; num_parents as usize];
+        let mut parent_max_keys: Vec<uint32_t> =
+            vec![0; num_parents as usize];
+        let mut child: uint32_t = 0 as libc::c_int as uint32_t;
+        let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+        while i < num_parents {
+            pager_release(new_pager);
+            let mut num_children: uint32_t =
+                num_nodes.wrapping_div(num_parents);
+            if i < num_nodes.wrapping_rem(num_parents) {
+                num_children = num_children.wrapping_add(1)
+            }
+            let mut page_num: uint32_t = root_page_num;
+            if num_parents > 1 as libc::c_int as libc::c_uint {
+                page_num = get_unused_page_num(new_pager)?
+            }
+            let mut node: *mut libc::c_void = get_page(new_pager, page_num)?;
+            pager_mark_dirty(new_pager, page_num)?;
+            initialize_internal_node(node);
+            *internal_node_num_keys(node) =
+                num_children.wrapping_sub(1 as libc::c_int as libc::c_uint);
+            let mut j_0: uint32_t = 0 as libc::c_int as uint32_t;
+            while j_0 < num_children {
+                *internal_node_child(node, j_0) = node_pages[child as usize];
+                if j_0 < *internal_node_num_keys(node) {
+                    *internal_node_key(node, j_0) =
+                        node_max_keys[child as usize]
+                }
+                *node_parent(get_page(new_pager,
+                                      node_pages[child as usize])?) =
+                    page_num;
+                pager_mark_dirty(new_pager, node_pages[child as usize])?;
+                child = child.wrapping_add(1);
+                j_0 = j_0.wrapping_add(1)
+            }
+            parent_pages[i as usize] = page_num;
+            parent_max_keys[i as usize] =
+                node_max_keys[child.wrapping_sub(1 as libc::c_int as
+                                                     libc::c_uint) as usize];
+            i = i.wrapping_add(1)
+        }
+        node_pages = parent_pages;
+        node_max_keys = parent_max_keys;
+        num_nodes = num_parents
+    }
+    set_node_root(get_page(new_pager, root_page_num)?, 1 as libc::c_int != 0);
+    pager_mark_dirty(new_pager, root_page_num)?;
+    return Ok(root_page_num);
+}
+
+pub(crate) unsafe fn vacuum_fill_leaves(mut cursor: *mut Cursor,
+                                 mut new_pager: *mut Pager,
+                                 mut roots: *mut Vec<uint32_t>,
+                                 mut root_page_num: uint32_t,
+                                 mut total_size: uint64_t,
+                                 mut node_pages: *mut Vec<uint32_t>,
+                                 mut node_max_keys: *mut Vec<uint32_t>)
+ -> Result<(), Error> {
+    /* Copy the rows from the cursor on into new leaves, noting each leaf */
+    let mut table: *mut Table = (*cursor).table;
+    let mut pager: *mut Pager = (*table).pager;
+    let mut space: uint64_t = (*pager).leaf_node_space_for_cells as uint64_t;
+    let mut remaining_size: uint64_t = total_size;
+    let mut leaf_page_num: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut leaf_size: uint64_t = 0 as libc::c_int as uint64_t;
+    let mut target_size: uint64_t = 0 as libc::c_int as uint64_t;
+    let mut row: Row = Row{values: Vec::new(),};
+    let mut row_num: usize = 0 as libc::c_int as usize;
+    /* Where the catalog keeps the root page of each table */
+    let mut root_column: libc::c_int =
+        schema_column_index((*table).schema,
+                            b"root\x00" as *const u8 as *const libc::c_char);
+    while !(*cursor).end_of_table {
+        pager_release(pager);
+        pager_release(new_pager);
+        let mut old_node: *mut libc::c_void =
+            get_page(pager, (*cursor).page_num)?;
+        let mut key: uint32_t = *leaf_node_key(old_node, (*cursor).cell_num);
+        let mut size: uint64_t =
+            leaf_node_cell_size(pager, old_node,
+                                (*cursor).cell_num).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE)
+                as uint64_t;
+        let mut record: Vec<u8> = cursor_value(cursor)?;
+        if !roots.is_null() {
+            /* Integers are fixed size, so the record keeps its size */
+            if deserialize_row((*table).schema, &record, &mut row).is_err() {
+                return Err(Error::Corrupt((*cursor).page_num))
+            }
+            row.values[root_column as usize] =
+                Value::Integer(*(*roots).as_ptr().offset(row_num as isize) as
+                                   int64_t);
+            record = serialize_row((*table).schema, &mut row);
+            row_num = row_num.wrapping_add(1)
+        }
+        if leaf_page_num == 0 as libc::c_int as libc::c_uint ||
+               leaf_size >= target_size ||
+               leaf_size.wrapping_add(size) > space {
+            /* A lone leaf is the root, otherwise leaves follow the root */
+            let mut page_num: uint32_t = root_page_num;
+            if total_size > space {
+                page_num = get_unused_page_num(new_pager)?
+            }
+            let mut leaf: *mut libc::c_void = get_page(new_pager, page_num)?;
+            pager_mark_dirty(new_pager, page_num)?;
+            initialize_leaf_node(pager, leaf);
+            if leaf_page_num != 0 as libc::c_int as libc::c_uint {
+                let mut previous: *mut libc::c_void =
+                    get_page(new_pager, leaf_page_num)?;
+                pager_mark_dirty(new_pager, leaf_page_num)?;
+                *leaf_node_next_leaf(previous) = page_num
+            }
+            /* Aim for an even share of what is left */
+            let mut num_leaves: uint64_t =
+                remaining_size.wrapping_add(space).wrapping_sub(1 as
+                                                                    libc::c_int
+                                                                    as
+                                                                    uint64_t).wrapping_div(space);
+            target_size = remaining_size.wrapping_div(num_leaves);
+            leaf_page_num = page_num;
+            leaf_size = 0 as libc::c_int as uint64_t;
+            (*node_pages).push(page_num);
+            (*node_max_keys).push(0 as libc::c_int as uint32_t);
+        }
+        let mut cell: Vec<u8> =
+            leaf_node_build_cell(new_pager, key, &record)?;
+        let mut leaf: *mut libc::c_void = get_page(new_pager, leaf_page_num)?;
+        pager_mark_dirty(new_pager, leaf_page_num)?;
+        leaf_node_insert_cell(leaf, *leaf_node_num_cells(leaf), &cell);
+        leaf_size = leaf_size.wrapping_add(size);
+        remaining_size = remaining_size.wrapping_sub(size);
+        *(*node_max_keys).last_mut().unwrap() = key;
+        cursor_advance(cursor)?;
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn do_meta_command(mut input_buffer: *mut InputBuffer,
+                                         mut table: *mut Table,
+                                         out: &mut String)
+ -> Result<MetaCommandResult, Error> {
+    /* .exit is up to the caller, which owns the database */
+    if strcmp((*input_buffer).buffer,
+              b".btree\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        pager_begin_read((*table).pager)?;
+        catalog_load(table)?;
+        /* The catalog's tree first, then one for every table */
+        let mut trees: Vec<*mut Table> = vec![table];
+        trees.extend((*table).tables.iter());
+        for tree in trees.iter() {
+            writeln!(out, "Tree of {}:", schema_name((**tree).schema)).ok();
+            print_tree((*table).pager, (**tree).root_page_num,
+                       0 as libc::c_int as uint32_t, out)?;
+        }
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".tables\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        pager_begin_read((*table).pager)?;
+        catalog_load(table)?;
+        for child in (*table).tables.iter() {
+            writeln!(out, "{}", schema_name((**child).schema)).ok();
+        }
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".vacuum\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        if (*(*table).pager).in_transaction {
+            return Err(Error::TransactionActive)
+        }
+        /* Other connections would keep reading the replaced file */
+        pager_begin_read((*table).pager)?;
+        catalog_load(table)?;
+        if !pager_lock((*table).pager, LOCK_EXCLUSIVE, 1 as libc::c_int != 0)
+               || !pager_only_connection((*table).pager) {
+            return Err(Error::Locked)
+        }
+        db_vacuum(table)?;
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strncmp((*input_buffer).buffer,
+                      b".cache_size\x00" as *const u8 as *const libc::c_char,
+                      11 as libc::c_int as libc::c_ulong) == 0 as libc::c_int
+                  &&
+                  (*(*input_buffer).buffer.offset(11 as libc::c_int as isize)
+                       as libc::c_int == 0 as libc::c_int ||
+                       *(*input_buffer).buffer.offset(11 as libc::c_int as
+                                                          isize) as
+                           libc::c_int == ' ' as i32) {
+        /* .cache_size [pages] */
+        if *(*input_buffer).buffer.offset(11 as libc::c_int as isize) as
+               libc::c_int == ' ' as i32 {
+            let mut cache_size: libc::c_int =
+                atoi((*input_buffer).buffer.offset(12 as libc::c_int as
+                                                       isize));
+            if cache_size <= 0 as libc::c_int {
+                writeln!(out, "Cache size must be positive.").ok();
+                return Ok(META_COMMAND_SUCCESS)
+            }
+            pager_set_cache_size((*table).pager, cache_size as uint32_t)?;
+        }
+        writeln!(out, "Cache size: {} pages",
+                 (*(*table).pager).cache_size).ok();
+        writeln!(out, "Cached pages: {}", (*(*table).pager).num_cached).ok();
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".freelist\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        pager_begin_read((*table).pager)?;
+        print_freelist((*table).pager, out)?;
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".check\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        pager_begin_read((*table).pager)?;
+        catalog_load(table)?;
+        out.push_str(&check_integrity(table)?);
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".stats\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        writeln!(out, "Pages read: {}", (*(*table).pager).pages_read).ok();
+        writeln!(out, "Pages written: {}",
+                 (*(*table).pager).pages_written).ok();
+        writeln!(out, "WAL frames: {}", (*(*table).pager).wal_frames).ok();
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".checkpoint\x00" as *const u8 as *const libc::c_char)
+                  == 0 as libc::c_int {
+        if (*(*table).pager).in_transaction {
+            return Err(Error::TransactionActive)
+        }
+        pager_begin_write((*table).pager)?;
+        pager_checkpoint((*table).pager, 1 as libc::c_int != 0)?;
+        return Ok(META_COMMAND_SUCCESS)
+    } else if cfg!(feature = "torn-write") &&
+                  strncmp((*input_buffer).buffer,
+                          b".torn_write \x00" as *const u8 as
+                              *const libc::c_char,
+                          12 as libc::c_int as libc::c_ulong) ==
+                      0 as libc::c_int {
+        /*
+  .torn_write N
+  Crash test hook, only built with the torn-write feature: the
+  Nth write to the database, WAL or journal from now on only gets
+  half of its data onto the file and fails, as does everything
+  after it, leaving recovery to the next open.
+  */
+        let mut countdown: libc::c_int =
+            atoi((*input_buffer).buffer.offset(12 as libc::c_int as isize));
+        if countdown <= 0 as libc::c_int {
+            writeln!(out, "Write count must be positive.").ok();
+            return Ok(META_COMMAND_SUCCESS)
+        }
+        (*(*table).pager).torn_write_countdown = countdown as uint32_t;
+        return Ok(META_COMMAND_SUCCESS)
+    } else if strcmp((*input_buffer).buffer,
+                     b".constants\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        writeln!(out, "Constants:").ok();
+        print_constants((*table).pager, out);
+        return Ok(META_COMMAND_SUCCESS)
+    } else { return Ok(META_COMMAND_UNRECOGNIZED_COMMAND) };
+}
+
+thread_local! {
+    static STRTOK_SAVE: ::std::cell::Cell<*mut libc::c_char> =
+        const { ::std::cell::Cell::new(0 as *mut libc::c_char) };
+}
+
+pub(crate) unsafe fn strtok(mut s: *mut libc::c_char,
+                            mut delim: *const libc::c_char)
+ -> *mut libc::c_char {
+    /*
+  The C strtok keeps its place in one static shared by every
+  thread, so connections preparing statements at the same time
+  would read each other's input. Keep the place per thread.
+  */
+    return STRTOK_SAVE.with(|save| {
+                                let mut rest: *mut libc::c_char = save.get();
+                                let mut token: *mut libc::c_char =
+                                    strtok_r(s, delim, &mut rest);
+                                save.set(rest);
+                                token
+                            });
+}
+
+pub(crate) unsafe fn prepare_value(mut column: *mut Column,
+                            mut token: *mut libc::c_char,
+                            mut value: *mut Value) -> PrepareResult {
+    /*
+  A literal for the column's type: an integer, a real, text as
+  it is, x'HEX' for a blob, or true or false. null is NULL in
+  every column.
+  */
+    let mut literal: &[u8] = std::ffi::CStr::from_ptr(token).to_bytes();
+    if literal == b"null" {
+        *value = Value::Null;
+        return PrepareResult::Success
+    }
+    let mut number: &str = std::str::from_utf8(literal).unwrap_or("");
+    match (*column).type_0 {
+        COLUMN_TYPE_INTEGER => {
+            match number.parse::<int64_t>() {
+                Ok(integer) => { *value = Value::Integer(integer) }
+                Err(_) => { return PrepareResult::TypeMismatch }
+            }
+        }
+        COLUMN_TYPE_REAL => {
+            match number.parse::<f64>() {
+                Ok(real) if real.is_finite() => { *value = Value::Real(real) }
+                _ => { return PrepareResult::TypeMismatch }
+            }
+        }
+        COLUMN_TYPE_TEXT => {
+            if !column_fits(column, literal.len()) {
+                return PrepareResult::StringTooLong
+            }
+            *value =
+                Value::Text(String::from_utf8_lossy(literal).into_owned())
+        }
+        COLUMN_TYPE_BLOB => {
+            if literal.len() < 3 as libc::c_int as usize ||
+                   !literal.starts_with(b"x'") || !literal.ends_with(b"'") ||
+                   literal.len() % 2 as libc::c_int as usize !=
+                       1 as libc::c_int as usize {
+                return PrepareResult::TypeMismatch
+            }
+            let mut blob: Vec<u8> = Vec::new();
+            for pair in literal[2..literal.len() - 1].chunks(2) {
+                match std::str::from_utf8(pair).ok().and_then(|hex|
+                                                                   u8::from_str_radix(hex,
+                                                                                      16).ok())
+                    {
+                    Some(byte) => { blob.push(byte) }
+                    None => { return PrepareResult::TypeMismatch }
+                }
+            }
+            if !column_fits(column, blob.len()) {
+                return PrepareResult::StringTooLong
+            }
+            *value = Value::Blob(blob)
+        }
+        _ => {
+            if literal == b"true" {
+                *value = Value::Boolean(1 as libc::c_int != 0)
+            } else if literal == b"false" {
+                *value = Value::Boolean(0 as libc::c_int != 0)
+            } else { return PrepareResult::TypeMismatch }
+        }
+    }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_key(mut schema: *mut Schema,
+                          mut token: *mut libc::c_char,
+                          mut key: *mut uint32_t) -> PrepareResult {
+    /* A key in a where clause, checked like the key of an insert */
+    let mut value: Value = Value::Null;
+    let mut result: PrepareResult =
+        prepare_value((*schema).columns.as_mut_ptr(), token, &mut value);
+    if result == PrepareResult::Success {
+        result = row_check_key(&mut value)
+    }
+    if result != PrepareResult::Success {
+        return result
+    }
+    if let Value::Integer(integer) = value { *key = integer as uint32_t }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_table(mut statement: *mut Statement,
+                            mut catalog: *mut Table,
+                            mut name: *const libc::c_char,
+                            mut writes: bool) -> PrepareResult {
+    /* Name the table a statement works on, which has to exist */
+    if name.is_null() { return PrepareResult::SyntaxError }
+    if strlen(name) > TABLE_NAME_SIZE as libc::c_ulong {
+        return PrepareResult::NoSuchTable
+    }
+    strcpy((*statement).table_name.as_mut_ptr(), name);
+    let mut table: *mut Table = catalog_find(catalog, name);
+    if table.is_null() { return PrepareResult::NoSuchTable }
+    /* Its rows are kept in step with the tables by CREATE TABLE */
+    if writes && table == catalog { return PrepareResult::ReadOnly }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_insert(mut input_buffer: *mut InputBuffer,
+                                        mut statement: *mut Statement,
+                                        mut catalog: *mut Table)
+ -> PrepareResult {
+    /* insert into TABLE V1 V2 ..., one value for each column */
+    (*statement).type_0 = STATEMENT_INSERT;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut into: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if into.is_null() ||
+           strcmp(into, b"into\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int {
+        return PrepareResult::SyntaxError
+    }
+    let mut result: PrepareResult =
+        prepare_table(statement, catalog,
+                      strtok(0 as *mut libc::c_char,
+                             b" \x00" as *const u8 as *const libc::c_char),
+                      1 as libc::c_int != 0);
+    if result != PrepareResult::Success {
+        return result
+    }
+    let mut schema: *mut Schema =
+        (*catalog_find(catalog, (*statement).table_name.as_ptr())).schema;
+    (*statement).row_to_insert.values.clear();
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < (*schema).columns.len() {
+        let mut token: *mut libc::c_char =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char);
+        if token.is_null() { return PrepareResult::SyntaxError }
+        let mut value: Value = Value::Null;
+        let mut result: PrepareResult =
+            prepare_value((*schema).columns.as_mut_ptr().offset(i as isize),
+                          token, &mut value);
+        if result as libc::c_uint ==
+               PrepareResult::Success as libc::c_int as libc::c_uint &&
+               i == 0 as libc::c_int as usize {
+            result = row_check_key(&mut value)
+        }
+        if result as libc::c_uint !=
+               PrepareResult::Success as libc::c_int as libc::c_uint {
+            return result
+        }
+        (*statement).row_to_insert.values.push(value);
+        i = i.wrapping_add(1)
+    }
+    if !strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_delete(mut input_buffer: *mut InputBuffer,
+                                        mut statement: *mut Statement,
+                                        mut catalog: *mut Table)
+ -> PrepareResult {
+    /*
+  delete from TABLE where KEY = N
+  delete from TABLE where KEY < N (also <=, > and >=)
+  delete from TABLE where KEY between N and M
+  KEY is the name of the first column.
+  */
+    (*statement).type_0 = STATEMENT_DELETE;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut from: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if from.is_null() ||
+           strcmp(from, b"from\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int {
+        return PrepareResult::SyntaxError
+    }
+    let mut result: PrepareResult =
+        prepare_table(statement, catalog,
+                      strtok(0 as *mut libc::c_char,
+                             b" \x00" as *const u8 as *const libc::c_char),
+                      1 as libc::c_int != 0);
+    if result != PrepareResult::Success {
+        return result
+    }
+    let mut schema: *mut Schema =
+        (*catalog_find(catalog, (*statement).table_name.as_ptr())).schema;
+    let mut where_0: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut column: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut operator: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut id_string: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if where_0.is_null() || column.is_null() || operator.is_null() ||
+           id_string.is_null() {
+        return PrepareResult::SyntaxError
+    }
+    if strcmp(where_0, b"where\x00" as *const u8 as *const libc::c_char) !=
+           0 as libc::c_int ||
+           schema_column_index(schema, column) != 0 as libc::c_int {
+        return PrepareResult::SyntaxError
+    }
+    let mut id: uint32_t = 0;
+    result = prepare_key(schema, id_string, &mut id);
+    if result != PrepareResult::Success {
+        return result
+    }
+    let mut rest: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if strcmp(operator, b"=\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        (*statement).min_id = id;
+        (*statement).max_id = id
+    } else if strcmp(operator, b"<\x00" as *const u8 as *const libc::c_char)
+                  == 0 as libc::c_int {
+        if id == 0 as libc::c_int as libc::c_uint {
+            /* Empty range */
+            (*statement).min_id = 1 as libc::c_int as uint32_t;
+            (*statement).max_id = 0 as libc::c_int as uint32_t
+        } else {
+            (*statement).min_id = 0 as libc::c_int as uint32_t;
+            (*statement).max_id =
+                id.wrapping_sub(1 as libc::c_int as libc::c_uint)
+        }
+    } else if strcmp(operator,
+                     b"<=\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        (*statement).min_id = 0 as libc::c_int as uint32_t;
+        (*statement).max_id = id
+    } else if strcmp(operator, b">\x00" as *const u8 as *const libc::c_char)
+                  == 0 as libc::c_int {
+        if id == 0xffffffff as libc::c_uint {
+            /* Empty range */
+            (*statement).min_id = 1 as libc::c_int as uint32_t;
+            (*statement).max_id = 0 as libc::c_int as uint32_t
+        } else {
+            (*statement).min_id =
+                id.wrapping_add(1 as libc::c_int as libc::c_uint);
+            (*statement).max_id = 0xffffffff as libc::c_uint
+        }
+    } else if strcmp(operator,
+                     b">=\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        (*statement).min_id = id;
+        (*statement).max_id = 0xffffffff as libc::c_uint
+    } else if strcmp(operator,
+                     b"between\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        let mut max_string: *mut libc::c_char =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char);
+        if rest.is_null() || max_string.is_null() ||
+               strcmp(rest, b"and\x00" as *const u8 as *const libc::c_char)
+                   != 0 as libc::c_int {
+            return PrepareResult::SyntaxError
+        }
+        let mut max_id: uint32_t = 0;
+        result = prepare_key(schema, max_string, &mut max_id);
+        if result as libc::c_uint !=
+               PrepareResult::Success as libc::c_int as libc::c_uint {
+            return result
+        }
+        (*statement).min_id = id;
+        (*statement).max_id = max_id;
+        rest =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char)
+    } else { return PrepareResult::SyntaxError }
+    if !rest.is_null() { return PrepareResult::SyntaxError }
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_update(mut input_buffer: *mut InputBuffer,
+                                        mut statement: *mut Statement,
+                                        mut catalog: *mut Table)
+ -> PrepareResult {
+    /*
+  update TABLE set COLUMN = V, COLUMN = V ... where KEY = N
+  Any column but the key may be assigned, the others keep
+  their values.
+  */
+    (*statement).type_0 = STATEMENT_UPDATE;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut result: PrepareResult =
+        prepare_table(statement, catalog,
+                      strtok(0 as *mut libc::c_char,
+                             b" \x00" as *const u8 as *const libc::c_char),
+                      1 as libc::c_int != 0);
+    if result != PrepareResult::Success {
+        return result
+    }
+    let mut schema: *mut Schema =
+        (*catalog_find(catalog, (*statement).table_name.as_ptr())).schema;
+    let mut set: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if set.is_null() ||
+           strcmp(set, b"set\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int {
+        return PrepareResult::SyntaxError
+    }
+    let mut num_columns: usize = (*schema).columns.len();
+    let mut values: Vec<Value> =
+        vec![Value::Null; num_columns];
+    let mut set_columns: Vec<bool> = vec![false; num_columns];
+    let mut column: *mut libc::c_char = 0 as *mut libc::c_char;
+    loop  {
+        column =
+            strtok(0 as *mut libc::c_char,
+                   b" ,\x00" as *const u8 as *const libc::c_char);
+        if column.is_null() ||
+               strcmp(column, b"where\x00" as *const u8 as *const libc::c_char)
+                   == 0 as libc::c_int {
+            break ;
+        }
+        let mut equals: *mut libc::c_char =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char);
+        let mut value: *mut libc::c_char =
+            strtok(0 as *mut libc::c_char,
+                   b" ,\x00" as *const u8 as *const libc::c_char);
+        if equals.is_null() || value.is_null() ||
+               strcmp(equals, b"=\x00" as *const u8 as *const libc::c_char)
+                   != 0 as libc::c_int {
+            return PrepareResult::SyntaxError
+        }
+        let mut index: libc::c_int = schema_column_index(schema, column);
+        if index <= 0 as libc::c_int { return PrepareResult::SyntaxError }
+        result =
+            prepare_value((*schema).columns.as_mut_ptr().offset(index as
+                                                                    isize),
+                          value, &mut values[index as usize]);
+        if result as libc::c_uint !=
+               PrepareResult::Success as libc::c_int as libc::c_uint {
+            return result
+        }
+        set_columns[index as usize] = 1 as libc::c_int != 0
+    }
+    if column.is_null() || !set_columns.contains(&(1 as libc::c_int != 0)) {
+        return PrepareResult::SyntaxError
+    }
+    /* The row is picked by its key */
+    let mut key: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut equals_0: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut id_string: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if key.is_null() || equals_0.is_null() || id_string.is_null() ||
+           schema_column_index(schema, key) != 0 as libc::c_int ||
+           strcmp(equals_0, b"=\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int ||
+           !strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    let mut id: uint32_t = 0;
+    result = prepare_key(schema, id_string, &mut id);
+    if result != PrepareResult::Success {
+        return result
+    }
+    values[0 as libc::c_int as usize] = Value::Integer(id as int64_t);
+    (*statement).row_to_insert.values = values;
+    (*statement).set_columns = set_columns;
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_select(mut input_buffer: *mut InputBuffer,
+                             mut statement: *mut Statement,
+                             mut catalog: *mut Table) -> PrepareResult {
+    /* select * from TABLE */
+    (*statement).type_0 = STATEMENT_SELECT;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut star: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut from: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut name: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if star.is_null() || from.is_null() ||
+           strcmp(star, b"*\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int ||
+           strcmp(from, b"from\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int ||
+           !strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    return prepare_table(statement, catalog, name, 0 as libc::c_int != 0);
+}
+
+pub(crate) unsafe fn prepare_pragma(mut input_buffer: *mut InputBuffer,
+                             mut statement: *mut Statement)
+ -> PrepareResult {
+    /*
+  pragma NAME          show the current value
+  pragma NAME = VALUE  change it
+  */
+    (*statement).type_0 = STATEMENT_PRAGMA;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut name: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" =\x00" as *const u8 as *const libc::c_char);
+    let mut value: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" =\x00" as *const u8 as *const libc::c_char);
+    if name.is_null() ||
+           !strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    (*statement).pragma_set = !value.is_null();
+    if strcmp(name, b"journal_mode\x00" as *const u8 as *const libc::c_char)
+           == 0 as libc::c_int {
+        (*statement).pragma = PRAGMA_JOURNAL_MODE;
+        if !value.is_null() {
+            match ::std::ffi::CStr::from_ptr(value).to_string_lossy().parse() {
+                Ok(journal_mode) => {
+                    (*statement).pragma_journal_mode = journal_mode
+                }
+                Err(UnknownSetting(_)) => { return PrepareResult::SyntaxError }
+            }
+        }
+        return PrepareResult::Success
+    }
+    if strcmp(name, b"synchronous\x00" as *const u8 as *const libc::c_char)
+           == 0 as libc::c_int {
+        (*statement).pragma = PRAGMA_SYNCHRONOUS;
+        if !value.is_null() {
+            match ::std::ffi::CStr::from_ptr(value).to_string_lossy().parse() {
+                Ok(synchronous) => {
+                    (*statement).pragma_synchronous = synchronous
+                }
+                Err(UnknownSetting(_)) => { return PrepareResult::SyntaxError }
+            }
+        }
+        return PrepareResult::Success
+    }
+    if strcmp(name, b"busy_timeout\x00" as *const u8 as *const libc::c_char)
+           == 0 as libc::c_int {
+        (*statement).pragma = PRAGMA_BUSY_TIMEOUT;
+        if !value.is_null() {
+            let mut end: *mut libc::c_char = 0 as *mut libc::c_char;
+            let mut timeout: libc::c_long =
+                strtol(value, &mut end, 10 as libc::c_int);
+            if *end as libc::c_int != 0 as libc::c_int ||
+                   timeout < 0 as libc::c_int as libc::c_long {
+                return PrepareResult::SyntaxError
+            }
+            (*statement).pragma_busy_timeout = timeout as uint32_t
+        }
+        return PrepareResult::Success
+    }
+    if strcmp(name, b"page_size\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        (*statement).pragma = PRAGMA_PAGE_SIZE;
+        if !value.is_null() { return PrepareResult::SyntaxError }
+        return PrepareResult::Success
+    }
+    return PrepareResult::UnrecognizedPragma;
+}
+
+pub(crate) unsafe fn prepare_savepoint(mut input_buffer: *mut InputBuffer,
+                                mut statement: *mut Statement)
+ -> PrepareResult {
+    /*
+  savepoint NAME
+  release [savepoint] NAME
+  rollback to [savepoint] NAME
+  */
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if strcmp(keyword, b"savepoint\x00" as *const u8 as *const libc::c_char)
+           == 0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_SAVEPOINT
+    } else if strcmp(keyword,
+                     b"release\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_RELEASE
+    } else if strcmp(keyword,
+                     b"rollback\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_ROLLBACK_TO;
+        let mut to: *mut libc::c_char =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char);
+        if to.is_null() ||
+               strcmp(to, b"to\x00" as *const u8 as *const libc::c_char) !=
+                   0 as libc::c_int {
+            return PrepareResult::SyntaxError
+        }
+    } else { return PrepareResult::UnrecognizedStatement }
+    let mut name: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut rest: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if !name.is_null() && !rest.is_null() &&
+           (*statement).type_0 as libc::c_uint !=
+               STATEMENT_SAVEPOINT as libc::c_int as libc::c_uint &&
+           strcmp(name, b"savepoint\x00" as *const u8 as *const libc::c_char)
+               == 0 as libc::c_int {
+        name = rest;
+        rest =
+            strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char)
+    }
+    if name.is_null() || !rest.is_null() { return PrepareResult::SyntaxError }
+    if strlen(name) > SAVEPOINT_NAME_SIZE as libc::c_ulong {
+        return PrepareResult::StringTooLong
+    }
+    strcpy((*statement).savepoint_name.as_mut_ptr(), name);
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_create_table(mut input_buffer: *mut InputBuffer,
+                                   mut statement: *mut Statement)
+ -> PrepareResult {
+    /*
+  create table NAME (COLUMN TYPE, COLUMN TYPE ...)
+  TYPE is any prepare_column takes. The first column is the key
+  and has to be an integer.
+  */
+    (*statement).type_0 = STATEMENT_CREATE_TABLE;
+    let mut schema: *mut Schema = &mut (*statement).schema;
+    /* The columns are between the first ( and the ) ending the line */
+    let mut open_paren: *mut libc::c_char =
+        strchr((*input_buffer).buffer, '(' as i32);
+    let mut close_paren: *mut libc::c_char =
+        strrchr((*input_buffer).buffer, ')' as i32);
+    if open_paren.is_null() || close_paren.is_null() ||
+           close_paren < open_paren {
+        return PrepareResult::SyntaxError
+    }
+    let mut rest: *mut libc::c_char =
+        close_paren.offset(1 as libc::c_int as isize);
+    while *rest as libc::c_int == ' ' as i32 {
+        rest = rest.offset(1 as libc::c_int as isize)
+    }
+    if *rest as libc::c_int != 0 as libc::c_int {
+        return PrepareResult::SyntaxError
+    }
+    *open_paren = 0 as libc::c_int as libc::c_char;
+    *close_paren = 0 as libc::c_int as libc::c_char;
+    let mut keyword: *mut libc::c_char =
+        strtok((*input_buffer).buffer,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut table_keyword: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    let mut name: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" \x00" as *const u8 as *const libc::c_char);
+    if table_keyword.is_null() || name.is_null() ||
+           strcmp(table_keyword,
+                  b"table\x00" as *const u8 as *const libc::c_char) !=
+               0 as libc::c_int ||
+           !strtok(0 as *mut libc::c_char,
+                   b" \x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    if strlen(name) > TABLE_NAME_SIZE as libc::c_ulong {
+        return PrepareResult::StringTooLong
+    }
+    strcpy((*schema).name.as_mut_ptr(), name);
+    let mut definition: *mut libc::c_char =
+        open_paren.offset(1 as libc::c_int as isize);
+    loop  {
+        let mut comma: *mut libc::c_char = strchr(definition, ',' as i32);
+        if !comma.is_null() { *comma = 0 as libc::c_int as libc::c_char }
+        let mut result: PrepareResult = prepare_column(definition, schema);
+        if result as libc::c_uint !=
+               PrepareResult::Success as libc::c_int as libc::c_uint {
+            return result
+        }
+        if comma.is_null() { break ; }
+        definition = comma.offset(1 as libc::c_int as isize)
+    }
+    if (*(*schema).columns.as_ptr()).type_0 !=
+           COLUMN_TYPE_INTEGER {
+        return PrepareResult::SyntaxError
+    }
+    let mut columns: Vec<String> = Vec::new();
+    for column in (*schema).columns.iter() {
+        let mut column_name =
+            std::ffi::CStr::from_ptr(column.name.as_ptr() as
+                                         *const libc::c_char).to_string_lossy();
+        match column.type_0 {
+            COLUMN_TYPE_INTEGER => {
+                columns.push(format!("{} integer", column_name))
+            }
+            COLUMN_TYPE_REAL => { columns.push(format!("{} real", column_name)) }
+            COLUMN_TYPE_TEXT if column.size == 0 as libc::c_int as libc::c_uint => {
+                columns.push(format!("{} text", column_name))
+            }
+            COLUMN_TYPE_TEXT => {
+                columns.push(format!("{} text({})", column_name, column.size))
+            }
+            COLUMN_TYPE_BLOB if column.size == 0 as libc::c_int as libc::c_uint => {
+                columns.push(format!("{} blob", column_name))
+            }
+            COLUMN_TYPE_BLOB => {
+                columns.push(format!("{} blob({})", column_name, column.size))
+            }
+            _ => { columns.push(format!("{} boolean", column_name)) }
+        }
+    }
+    (*schema).sql =
+        format!("create table {} ({})",
+                std::ffi::CStr::from_ptr(name).to_string_lossy(),
+                columns.join(", "));
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_column(mut definition: *mut libc::c_char,
+                             mut schema: *mut Schema) -> PrepareResult {
+    /*
+  NAME integer, NAME real, NAME boolean, NAME text or NAME blob,
+  the last two optionally limited to N bytes as text(N) and
+  blob(N), appended to the row
+  */
+    let mut name: *mut libc::c_char =
+        strtok(definition, b" \x00" as *const u8 as *const libc::c_char);
+    let mut type_name: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" (\x00" as *const u8 as *const libc::c_char);
+    let mut size_string: *mut libc::c_char =
+        strtok(0 as *mut libc::c_char,
+               b" ()\x00" as *const u8 as *const libc::c_char);
+    if name.is_null() || type_name.is_null() ||
+           !strtok(0 as *mut libc::c_char,
+                   b" ()\x00" as *const u8 as *const libc::c_char).is_null() {
+        return PrepareResult::SyntaxError
+    }
+    if strlen(name) > COLUMN_NAME_SIZE as libc::c_ulong {
+        return PrepareResult::StringTooLong
+    }
+    if (*schema).columns.len() >= TABLE_MAX_COLUMNS ||
+           schema_column_index(schema, name) != -(1 as libc::c_int) {
+        return PrepareResult::SyntaxError
+    }
+    let mut column: Column =
+        Column{name: [0; COLUMN_NAME_SIZE + 1],
+               type_0: COLUMN_TYPE_INTEGER,
+               size: 0,};
+    strcpy(column.name.as_mut_ptr(), name);
+    let mut is_text: bool =
+        strcmp(type_name, b"text\x00" as *const u8 as *const libc::c_char) ==
+            0 as libc::c_int;
+    if strcmp(type_name, b"integer\x00" as *const u8 as *const libc::c_char)
+           == 0 as libc::c_int && size_string.is_null() {
+        column.type_0 = COLUMN_TYPE_INTEGER
+    } else if strcmp(type_name,
+                     b"real\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int && size_string.is_null() {
+        column.type_0 = COLUMN_TYPE_REAL
+    } else if strcmp(type_name,
+                     b"boolean\x00" as *const u8 as *const libc::c_char) ==
+                  0 as libc::c_int && size_string.is_null() {
+        column.type_0 = COLUMN_TYPE_BOOLEAN
+    } else if is_text ||
+                  strcmp(type_name,
+                         b"blob\x00" as *const u8 as *const libc::c_char) ==
+                      0 as libc::c_int {
+        if !size_string.is_null() {
+            let mut end: *mut libc::c_char = 0 as *mut libc::c_char;
+            let mut length: libc::c_long =
+                strtol(size_string, &mut end, 10 as libc::c_int);
+            if *end as libc::c_int != 0 as libc::c_int ||
+                   length <= 0 as libc::c_int as libc::c_long {
+                return PrepareResult::SyntaxError
+            }
+            if length > VALUE_MAX_SIZE as libc::c_long {
+                return PrepareResult::StringTooLong
+            }
+            column.size = length as uint32_t
+        }
+        if is_text {
+            column.type_0 = COLUMN_TYPE_TEXT
+        } else { column.type_0 = COLUMN_TYPE_BLOB }
+    } else { return PrepareResult::SyntaxError }
+    (*schema).columns.push(column);
+    return PrepareResult::Success;
+}
+
+pub(crate) unsafe fn prepare_statement(mut input_buffer: *mut InputBuffer,
+                                           mut statement: *mut Statement,
+                                           mut catalog: *mut Table)
+ -> PrepareResult {
+    /* Values are checked against the schema the named table has now */
+    if strncmp((*input_buffer).buffer,
+               b"insert\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_insert(input_buffer, statement, catalog)
+    }
+    if strncmp((*input_buffer).buffer,
+               b"select\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_select(input_buffer, statement, catalog)
+    }
+    if strncmp((*input_buffer).buffer,
+               b"delete\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_delete(input_buffer, statement, catalog)
+    }
+    if strncmp((*input_buffer).buffer,
+               b"update\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_update(input_buffer, statement, catalog)
+    }
+    if strncmp((*input_buffer).buffer,
+               b"create\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_create_table(input_buffer, statement)
+    }
+    if strncmp((*input_buffer).buffer,
+               b"pragma\x00" as *const u8 as *const libc::c_char,
+               6 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_pragma(input_buffer, statement)
+    }
+    if strcmp((*input_buffer).buffer,
+              b"begin\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_BEGIN;
+        return PrepareResult::Success
+    }
+    if strcmp((*input_buffer).buffer,
+              b"commit\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_COMMIT;
+        return PrepareResult::Success
+    }
+    if strcmp((*input_buffer).buffer,
+              b"rollback\x00" as *const u8 as *const libc::c_char) ==
+           0 as libc::c_int {
+        (*statement).type_0 = STATEMENT_ROLLBACK;
+        return PrepareResult::Success
+    }
+    if strncmp((*input_buffer).buffer,
+               b"savepoint\x00" as *const u8 as *const libc::c_char,
+               9 as libc::c_int as libc::c_ulong) == 0 as libc::c_int ||
+           strncmp((*input_buffer).buffer,
+                   b"release\x00" as *const u8 as *const libc::c_char,
+                   7 as libc::c_int as libc::c_ulong) == 0 as libc::c_int ||
+           strncmp((*input_buffer).buffer,
+                   b"rollback \x00" as *const u8 as *const libc::c_char,
+                   9 as libc::c_int as libc::c_ulong) == 0 as libc::c_int {
+        return prepare_savepoint(input_buffer, statement)
+    }
+    return PrepareResult::UnrecognizedStatement;
+}
+/*
+Pages freed by deletes are recycled first.
+Only when the free list is empty does a new page
+go onto the end of the database file.
+*/
+
+pub(crate) unsafe fn get_unused_page_num(mut pager: *mut Pager)
+ -> Result<uint32_t, Error> {
+    let mut header: *mut libc::c_void =
+        get_page(pager, 0 as libc::c_int as uint32_t)?;
+    if *file_header_freelist_count(header) == 0 as libc::c_int as libc::c_uint
+       {
+        return Ok((*pager).num_pages)
+    }
+    pager_mark_dirty(pager, 0 as libc::c_int as uint32_t)?;
+    *file_header_freelist_count(header) =
+        (*file_header_freelist_count(header)).wrapping_sub(1 as libc::c_int as
+                                                               libc::c_uint);
+    let mut trunk_page_num: uint32_t = *file_header_freelist_trunk(header);
+    let mut trunk: *mut libc::c_void = get_page(pager, trunk_page_num)?;
+    let mut num_leaves: uint32_t = *freelist_trunk_num_leaves(trunk);
+    if num_leaves > 0 as libc::c_int as libc::c_uint {
+        num_leaves = num_leaves.wrapping_sub(1 as libc::c_int as libc::c_uint);
+        *freelist_trunk_num_leaves(trunk) = num_leaves;
+        pager_mark_dirty(pager, trunk_page_num)?;
+        return Ok(*freelist_trunk_leaf(trunk, num_leaves))
+    }
+    /* Trunk has no leaves left, hand out the trunk page itself */
+    *file_header_freelist_trunk(header) = *freelist_trunk_next(trunk);
+    return Ok(trunk_page_num);
+}
+
+pub(crate) unsafe fn free_page(mut pager: *mut Pager, mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Put a page no longer used by the tree on the free list.
+  It is recorded as a leaf of the first trunk page,
+  or becomes the new first trunk page when that one is full.
+  */
+    let mut header: *mut libc::c_void =
+        get_page(pager, 0 as libc::c_int as uint32_t)?;
+    pager_mark_dirty(pager, 0 as libc::c_int as uint32_t)?;
+    let mut trunk_page_num: uint32_t = *file_header_freelist_trunk(header);
+    if *file_header_freelist_count(header) > 0 as libc::c_int as libc::c_uint
+       {
+        let mut trunk: *mut libc::c_void = get_page(pager, trunk_page_num)?;
+        let mut num_leaves: uint32_t = *freelist_trunk_num_leaves(trunk);
+        if num_leaves < (*pager).freelist_trunk_max_leaves {
+            pager_mark_dirty(pager, trunk_page_num)?;
+            *freelist_trunk_leaf(trunk, num_leaves) = page_num;
+            *freelist_trunk_num_leaves(trunk) =
+                num_leaves.wrapping_add(1 as libc::c_int as libc::c_uint);
+            *file_header_freelist_count(header) =
+                (*file_header_freelist_count(header)).wrapping_add(1 as
+                                                                       libc::c_int
+                                                                       as
+                                                                       libc::c_uint);
+            return Ok(())
+        }
+    } else { trunk_page_num = 0 as libc::c_int as uint32_t }
+    let mut new_trunk: *mut libc::c_void = get_page(pager, page_num)?;
+    pager_mark_dirty(pager, page_num)?;
+    *freelist_trunk_next(new_trunk) = trunk_page_num;
+    *freelist_trunk_num_leaves(new_trunk) = 0 as libc::c_int as uint32_t;
+    *file_header_freelist_trunk(header) = page_num;
+    *file_header_freelist_count(header) =
+        (*file_header_freelist_count(header)).wrapping_add(1 as libc::c_int as
+                                                               libc::c_uint);
+    return Ok(());
+}
+
+pub(crate) unsafe fn overflow_write(mut pager: *mut Pager, mut data: &[u8])
+ -> Result<uint32_t, Error> {
+    /* Store the spilled part of a payload, returns the first page */
+    let mut first_page_num: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut previous_page_num: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut written: usize = 0 as libc::c_int as usize;
+    while written < data.len() {
+        let mut page_num: uint32_t = get_unused_page_num(pager)?;
+        let mut page: *mut libc::c_void = get_page(pager, page_num)?;
+        pager_mark_dirty(pager, page_num)?;
+        if previous_page_num == 0 as libc::c_int as libc::c_uint {
+            first_page_num = page_num
+        } else {
+            let mut previous: *mut libc::c_void =
+                get_page(pager, previous_page_num)?;
+            pager_mark_dirty(pager, previous_page_num)?;
+            *overflow_page_next(previous) = page_num
+        }
+        let mut size: usize =
+            ::std::cmp::min(data.len().wrapping_sub(written),
+                            (*pager).overflow_page_space as usize);
+        memset(page, 0 as libc::c_int, (*pager).usable_size as libc::c_ulong);
+        memcpy(page.offset(OVERFLOW_PAGE_HEADER_SIZE as isize),
+               data.as_ptr().offset(written as isize) as *const libc::c_void,
+               size as libc::c_ulong);
+        written = written.wrapping_add(size);
+        previous_page_num = page_num
+    }
+    return Ok(first_page_num);
+}
+
+pub(crate) unsafe fn overflow_read(mut pager: *mut Pager, mut referrer: uint32_t,
+                            mut page_num: uint32_t,
+                            mut payload_size: uint32_t,
+                            mut record: *mut Vec<u8>) -> Result<(), Error> {
+    /*
+  Append the spilled bytes to the part of the record kept on the
+  leaf. A broken chain is blamed on the page pointing off it.
+  */
+    while (*record).len() < payload_size as usize {
+        if page_num == 0 as libc::c_int as libc::c_uint ||
+               page_num >= (*pager).num_pages {
+            return Err(Error::Corrupt(referrer))
+        }
+        let mut page: *mut libc::c_void = get_page(pager, page_num)?;
+        let mut size: usize =
+            ::std::cmp::min((payload_size as usize).wrapping_sub((*record).len()),
+                            (*pager).overflow_page_space as usize);
+        (*record).extend_from_slice(::std::slice::from_raw_parts(page.offset(OVERFLOW_PAGE_HEADER_SIZE
+                                                                                 as
+                                                                                 isize)
+                                                                     as
+                                                                     *const u8,
+                                                                 size));
+        referrer = page_num;
+        page_num = *overflow_page_next(page)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn overflow_free(mut pager: *mut Pager, mut referrer: uint32_t,
+                            mut page_num: uint32_t,
+                            mut payload_size: uint32_t) -> Result<(), Error> {
+    /* Put the overflow pages of a payload on the free list */
+    let mut remaining: uint32_t =
+        payload_size.wrapping_sub(payload_local_size(pager, payload_size));
+    while remaining > 0 as libc::c_int as libc::c_uint {
+        if page_num == 0 as libc::c_int as libc::c_uint ||
+               page_num >= (*pager).num_pages {
+            return Err(Error::Corrupt(referrer))
+        }
+        /* Read the next page first, freeing may turn this one into a trunk */
+        let mut page: *mut libc::c_void = get_page(pager, page_num)?;
+        let mut next_page_num: uint32_t = *overflow_page_next(page);
+        free_page(pager, page_num)?;
+        referrer = page_num;
+        remaining =
+            remaining.wrapping_sub(::std::cmp::min(remaining,
+                                                   (*pager).overflow_page_space));
+        page_num = next_page_num
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn print_freelist(mut pager: *mut Pager, out: &mut String)
+ -> Result<(), Error> {
+    let mut header: *mut libc::c_void =
+        get_page(pager, 0 as libc::c_int as uint32_t)?;
+    let mut num_free: uint32_t = *file_header_freelist_count(header);
+    let mut num_trunks: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut trunk_page_num: uint32_t = *file_header_freelist_trunk(header);
+    if num_free == 0 as libc::c_int as libc::c_uint {
+        trunk_page_num = 0 as libc::c_int as uint32_t
+    }
+    while trunk_page_num != 0 as libc::c_int as libc::c_uint {
+        num_trunks = num_trunks.wrapping_add(1);
+        trunk_page_num =
+            *freelist_trunk_next(get_page(pager, trunk_page_num)?)
+    }
+    writeln!(out, "Free pages: {}", num_free).ok();
+    writeln!(out, "Trunk pages: {}", num_trunks).ok();
+    return Ok(());
+}
+
+pub(crate) unsafe fn check_read(mut check: *mut IntegrityCheck,
+                         mut page_num: uint32_t,
+                         mut page: *mut libc::c_void) -> Result<bool, Error> {
+    /*
+  Copy a page without going through get_page, so a bad page is
+  reported instead of failing the check and nothing is evicted.
+  Cached pages may be newer than storage inside a transaction.
+  */
+    let mut cached: *mut CachedPage = pager_lookup((*check).pager, page_num);
+    if !cached.is_null() {
+        memcpy(page, (*cached).data,
+               (*(*check).pager).page_size as libc::c_ulong);
+        return Ok(1 as libc::c_int != 0)
+    }
+    match pager_read_page((*check).pager, page_num, page) {
+        Err(Error::Corrupt(_)) => {
+            writeln!((*check).output, "Page {}: checksum mismatch",
+                     page_num).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1);
+            return Ok(0 as libc::c_int != 0)
+        }
+        Err(error) => { return Err(error) }
+        Ok(()) => { }
+    }
+    return Ok(1 as libc::c_int != 0);
+}
+
+pub(crate) unsafe fn check_claim(mut check: *mut IntegrityCheck,
+                          mut page_num: uint32_t, mut page_use: PageUse,
+                          mut referrer: uint32_t) -> bool {
+    /* Record what a page is used for, once */
+    if page_num == 0 as libc::c_int as libc::c_uint ||
+           page_num >= (*(*check).pager).num_pages {
+        writeln!((*check).output,
+                 "Page {}: refers to page {}, which is not in the database",
+                 referrer, page_num).ok();
+        (*check).num_problems = (*check).num_problems.wrapping_add(1);
+        return 0 as libc::c_int != 0
+    }
+    if *(*check).page_use.offset(page_num as isize) != PAGE_USE_NONE {
+        writeln!((*check).output,
+                 "Page {}: used more than once, again by page {}", page_num,
+                 referrer).ok();
+        (*check).num_problems = (*check).num_problems.wrapping_add(1);
+        return 0 as libc::c_int != 0
+    }
+    *(*check).page_use.offset(page_num as isize) = page_use;
+    return 1 as libc::c_int != 0;
+}
+
+pub(crate) unsafe fn check_node(mut check: *mut IntegrityCheck,
+                         mut page_num: uint32_t,
+                         mut parent_page_num: uint32_t,
+                         mut min_key: uint64_t, mut max_key: uint64_t,
+                         mut depth: uint32_t) -> Result<(), Error> {
+    /*
+  Check a subtree whose keys must lie within [min_key, max_key],
+  the bounds set by the separators above it.
+  */
+    let mut pager: *mut Pager = (*check).pager;
+    if !check_claim(check, page_num, PAGE_USE_TREE, parent_page_num) {
+        return Ok(())
+    }
+    let mut node: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    match check_read(check, page_num, node) {
+        Ok(true) => { }
+        Ok(false) => {
+            /* Its leaves are unknown, a 0 keeps the chain check from guessing */
+            *(*check).leaves.offset((*check).num_leaves as isize) =
+                0 as libc::c_int as uint32_t;
+            (*check).num_leaves = (*check).num_leaves.wrapping_add(1);
+            free(node);
+            return Ok(())
+        }
+        Err(error) => { free(node); return Err(error) }
+    }
+    let mut result: Result<(), Error> = Ok(());
+    let mut is_root: bool = page_num == (*check).root_page_num;
+    if is_node_root(node) != is_root {
+        if is_root {
+            writeln!((*check).output, "Page {}: root is not flagged as root",
+                     page_num).ok();
+        } else {
+            writeln!((*check).output, "Page {}: flagged as root",
+                     page_num).ok();
+        }
+        (*check).num_problems = (*check).num_problems.wrapping_add(1)
+    }
+    if !is_root && *node_parent(node) != parent_page_num {
+        writeln!((*check).output,
+                 "Page {}: parent pointer is {}, expected {}", page_num,
+                 *node_parent(node), parent_page_num).ok();
+        (*check).num_problems = (*check).num_problems.wrapping_add(1)
+    }
+    match get_node_type(node) {
+        NODE_LEAF => {
+            let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+            let mut content_offset: uint32_t =
+                *leaf_node_content_offset(node);
+            if !leaf_node_fits(pager, node) {
+                /* Cells cannot be located without the pointers */
+                writeln!((*check).output,
+                         "Page {}: {} cells and cell content from offset {} do not fit",
+                         page_num, num_cells, content_offset).ok();
+                (*check).num_problems = (*check).num_problems.wrapping_add(1);
+                num_cells = 0 as libc::c_int as uint32_t
+            }
+            let mut cells_size: uint32_t = 0 as libc::c_int as uint32_t;
+            let mut cells_ok: bool = 1 as libc::c_int != 0;
+            /* Keys are compared with the last cell that could be read */
+            let mut previous_key: Option<uint32_t> = None;
+            let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+            while i < num_cells {
+                if !leaf_node_cell_ok(pager, node, i) {
+                    writeln!((*check).output,
+                             "Page {}: cell {} lies outside the cell content",
+                             page_num, i).ok();
+                    (*check).num_problems =
+                        (*check).num_problems.wrapping_add(1);
+                    cells_ok = 0 as libc::c_int != 0;
+                    i = i.wrapping_add(1);
+                    continue ;
+                }
+                cells_size =
+                    cells_size.wrapping_add(leaf_node_cell_size(pager, node, i));
+                result = check_cell(check, page_num, node, i);
+                if result.is_err() { break ; }
+                let mut key: uint32_t = *leaf_node_key(node, i);
+                if let Some(previous_key) = previous_key {
+                    if key <= previous_key {
+                        writeln!((*check).output,
+                                 "Page {}: key {} is not greater than the key before it",
+                                 page_num, key).ok();
+                        (*check).num_problems =
+                            (*check).num_problems.wrapping_add(1)
+                    }
+                }
+                previous_key = Some(key);
+                if (key as uint64_t) < min_key || key as uint64_t > max_key {
+                    writeln!((*check).output,
+                             "Page {}: key {} is outside the range {}..{} set by its parent",
+                             page_num, key, min_key, max_key).ok();
+                    (*check).num_problems =
+                        (*check).num_problems.wrapping_add(1)
+                }
+                i = i.wrapping_add(1)
+            }
+            /* Deletes leave no gaps, the cells fill the content area */
+            if cells_ok && num_cells > 0 as libc::c_int as libc::c_uint &&
+                   cells_size != (*pager).usable_size.wrapping_sub(content_offset)
+               {
+                writeln!((*check).output,
+                         "Page {}: cells take {} bytes, the cell content has {}",
+                         page_num, cells_size,
+                         (*pager).usable_size.wrapping_sub(content_offset)).ok();
+                (*check).num_problems = (*check).num_problems.wrapping_add(1)
+            }
+            /* All leaves sit at the same depth */
+            if (*check).leaf_depth == 0 as libc::c_int as libc::c_uint {
+                (*check).leaf_depth = depth
+            } else if depth != (*check).leaf_depth {
+                writeln!((*check).output,
+                         "Page {}: leaf at depth {}, other leaves are at depth {}",
+                         page_num, depth, (*check).leaf_depth).ok();
+                (*check).num_problems = (*check).num_problems.wrapping_add(1)
+            }
+            *(*check).leaves.offset((*check).num_leaves as isize) = page_num;
+            *(*check).next_leaves.offset((*check).num_leaves as isize) =
+                *leaf_node_next_leaf(node);
+            (*check).num_leaves = (*check).num_leaves.wrapping_add(1)
+        }
+        NODE_INTERNAL => {
+            let mut num_keys: uint32_t = *internal_node_num_keys(node);
+            if num_keys > (*pager).internal_node_max_cells {
+                writeln!((*check).output,
+                         "Page {}: {} keys, at most {} fit", page_num,
+                         num_keys, (*pager).internal_node_max_cells).ok();
+                (*check).num_problems = (*check).num_problems.wrapping_add(1);
+                num_keys = (*pager).internal_node_max_cells
+            }
+            let mut child_min_key: uint64_t = min_key;
+            let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+            while i <= num_keys {
+                let mut child: uint32_t = 0;
+                let mut child_max_key: uint64_t = max_key;
+                if i < num_keys {
+                    child = *internal_node_cell(node, i);
+                    let mut key: uint32_t = *internal_node_key(node, i);
+                    if i > 0 as libc::c_int as libc::c_uint &&
+                           key <=
+                               *internal_node_key(node,
+                                                  i.wrapping_sub(1 as
+                                                                     libc::c_int
+                                                                     as
+                                                                     libc::c_uint))
+                       {
+                        writeln!((*check).output,
+                                 "Page {}: key {} is not greater than the key before it",
+                                 page_num, key).ok();
+                        (*check).num_problems =
+                            (*check).num_problems.wrapping_add(1)
+                    }
+                    if (key as uint64_t) < min_key ||
+                           key as uint64_t > max_key {
+                        writeln!((*check).output,
+                                 "Page {}: key {} is outside the range {}..{} set by its parent",
+                                 page_num, key, min_key, max_key).ok();
+                        (*check).num_problems =
+                            (*check).num_problems.wrapping_add(1)
+                    }
+                    child_max_key = key as uint64_t
+                } else { child = *internal_node_right_child(node) }
+                result =
+                    check_node(check, child, page_num, child_min_key,
+                               child_max_key,
+                               depth.wrapping_add(1 as libc::c_int as
+                                                      libc::c_uint));
+                if result.is_err() { break ; }
+                child_min_key =
+                    child_max_key.wrapping_add(1 as libc::c_int as uint64_t);
+                i = i.wrapping_add(1)
+            }
+        }
+        _ => {
+            writeln!((*check).output,
+                     "Page {}: unknown node type {}", page_num,
+                     get_node_type(node)).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1)
+        }
+    }
+    free(node);
+    return result;
+}
+
+pub(crate) unsafe fn check_cell(mut check: *mut IntegrityCheck,
+                         mut page_num: uint32_t, mut node: *mut libc::c_void,
+                         mut cell_num: uint32_t) -> Result<(), Error> {
+    /* Follow the overflow chain of a cell and match its row to the schema */
+    let mut pager: *mut Pager = (*check).pager;
+    let mut payload_size: uint32_t = *leaf_node_payload_size(node, cell_num);
+    let mut record: Vec<u8> =
+        ::std::slice::from_raw_parts(leaf_node_payload(pager, node, cell_num) as
+                                         *const u8,
+                                     payload_local_size(pager, payload_size) as
+                                         usize).to_vec();
+    if payload_spills(pager, payload_size) {
+        let mut overflow_page_num: uint32_t =
+            *leaf_node_overflow(node, cell_num);
+        let mut referrer: uint32_t = page_num;
+        let mut page: *mut libc::c_void =
+            calloc(1 as libc::c_int as libc::c_ulong,
+                   (*pager).page_size as libc::c_ulong);
+        while record.len() < payload_size as usize {
+            if !check_claim(check, overflow_page_num, PAGE_USE_OVERFLOW,
+                            referrer) {
+                free(page);
+                return Ok(())
+            }
+            match check_read(check, overflow_page_num, page) {
+                Ok(true) => { }
+                Ok(false) => { free(page); return Ok(()) }
+                Err(error) => { free(page); return Err(error) }
+            }
+            let mut size: usize =
+                ::std::cmp::min((payload_size as usize).wrapping_sub(record.len()),
+                                (*pager).overflow_page_space as usize);
+            record.extend_from_slice(::std::slice::from_raw_parts(page.offset(OVERFLOW_PAGE_HEADER_SIZE
+                                                                                  as
+                                                                                  isize)
+                                                                      as
+                                                                      *const u8,
+                                                                  size));
+            referrer = overflow_page_num;
+            overflow_page_num = *overflow_page_next(page)
+        }
+        free(page);
+        if overflow_page_num != 0 as libc::c_int as libc::c_uint {
+            writeln!((*check).output,
+                     "Page {}: overflow chain goes on to page {} past the end of the row",
+                     referrer, overflow_page_num).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1)
+        }
+    }
+    let mut row: Row = Row{values: Vec::new(),};
+    if deserialize_row((*check).schema, &record, &mut row).is_err() {
+        writeln!((*check).output,
+                 "Page {}: row with key {} does not match the schema",
+                 page_num, *leaf_node_key(node, cell_num)).ok();
+        (*check).num_problems = (*check).num_problems.wrapping_add(1)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn check_freelist(mut check: *mut IntegrityCheck)
+ -> Result<(), Error> {
+    let mut pager: *mut Pager = (*check).pager;
+    let mut page: *mut libc::c_void =
+        calloc(1 as libc::c_int as libc::c_ulong,
+               (*pager).page_size as libc::c_ulong);
+    match check_read(check, 0 as libc::c_int as uint32_t, page) {
+        Ok(true) => { }
+        Ok(false) => { free(page); return Ok(()) }
+        Err(error) => { free(page); return Err(error) }
+    }
+    let mut result: Result<(), Error> = Ok(());
+    let mut num_free: uint32_t = *file_header_freelist_count(page);
+    let mut trunk_page_num: uint32_t = *file_header_freelist_trunk(page);
+    /* The trunk pointer means nothing once the list is empty */
+    if num_free == 0 as libc::c_int as libc::c_uint {
+        trunk_page_num = 0 as libc::c_int as uint32_t
+    }
+    let mut num_listed: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut referrer: uint32_t = 0 as libc::c_int as uint32_t;
+    while trunk_page_num != 0 as libc::c_int as libc::c_uint {
+        if !check_claim(check, trunk_page_num, PAGE_USE_FREELIST, referrer) {
+            break ;
+        }
+        match check_read(check, trunk_page_num, page) {
+            Ok(true) => { }
+            Ok(false) => { break ; }
+            Err(error) => { result = Err(error); break ; }
+        }
+        num_listed = num_listed.wrapping_add(1);
+        let mut num_leaves: uint32_t = *freelist_trunk_num_leaves(page);
+        if num_leaves > (*pager).freelist_trunk_max_leaves {
+            writeln!((*check).output,
+                     "Page {}: free list trunk with {} leaves, at most {} fit",
+                     trunk_page_num, num_leaves, (*pager).freelist_trunk_max_leaves).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1);
+            num_leaves = (*pager).freelist_trunk_max_leaves
+        }
+        let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+        while i < num_leaves {
+            check_claim(check, *freelist_trunk_leaf(page, i),
+                        PAGE_USE_FREELIST, trunk_page_num);
+            num_listed = num_listed.wrapping_add(1);
+            i = i.wrapping_add(1)
+        }
+        referrer = trunk_page_num;
+        trunk_page_num = *freelist_trunk_next(page)
+    }
+    if result.is_ok() && num_listed != num_free {
+        writeln!((*check).output,
+                 "Page 0: free list holds {} pages, header says {}",
+                 num_listed, num_free).ok();
+        (*check).num_problems = (*check).num_problems.wrapping_add(1)
+    }
+    free(page);
+    return result;
+}
+
+pub(crate) unsafe fn check_integrity(mut catalog: *mut Table)
+ -> Result<String, Error> {
+    /*
+  .check walks the tree of the catalog and of every table from
+  its root, and the free list, and prints every problem it finds
+  rather than stopping at the first one: key order and separator
+  bounds, parent pointers, root flags, the leaf chain, cell
+  layout, overflow chains, rows that do not match their schema,
+  and pages that are lost or used twice.
+  */
+    let mut pager: *mut Pager = (*catalog).pager;
+    let mut check: IntegrityCheck =
+        IntegrityCheck{pager: pager,
+                       root_page_num: 0 as libc::c_int as uint32_t,
+                       schema: 0 as *mut Schema,
+                       page_use:
+                           calloc(1 as libc::c_int as libc::c_ulong,
+                                  (*pager).num_pages as libc::c_ulong) as
+                               *mut PageUse,
+                       leaves:
+                           calloc(1 as libc::c_int as libc::c_ulong,
+                                  ((*pager).num_pages as
+                                       libc::c_ulong).wrapping_mul(::std::mem::size_of::<uint32_t>()
+                                                                       as
+                                                                       libc::c_ulong))
+                               as *mut uint32_t,
+                       next_leaves:
+                           calloc(1 as libc::c_int as libc::c_ulong,
+                                  ((*pager).num_pages as
+                                       libc::c_ulong).wrapping_mul(::std::mem::size_of::<uint32_t>()
+                                                                       as
+                                                                       libc::c_ulong))
+                               as *mut uint32_t,
+                       num_leaves: 0 as libc::c_int as uint32_t,
+                       leaf_depth: 0 as libc::c_int as uint32_t,
+                       num_problems: 0 as libc::c_int as uint32_t,
+                       output: String::new(),};
+    memset(check.page_use as *mut libc::c_void, PAGE_USE_NONE as libc::c_int,
+           (*pager).num_pages as libc::c_ulong);
+    *check.page_use.offset(0 as libc::c_int as isize) = PAGE_USE_HEADER;
+    let mut tables: Vec<*mut Table> = vec![catalog];
+    tables.extend((*catalog).tables.iter());
+    let mut result: Result<(), Error> = Ok(());
+    for table in tables.iter() {
+        check.root_page_num = (**table).root_page_num;
+        check.schema = (**table).schema;
+        check.num_leaves = 0 as libc::c_int as uint32_t;
+        check.leaf_depth = 0 as libc::c_int as uint32_t;
+        result =
+            check_node(&mut check, check.root_page_num,
+                       0 as libc::c_int as uint32_t,
+                       0 as libc::c_int as uint64_t,
+                       0xffffffff as libc::c_uint as uint64_t,
+                       1 as libc::c_int as uint32_t);
+        if result.is_err() { break ; }
+        check_leaf_chain(&mut check);
+    }
+    if result.is_ok() { result = check_integrity_report(&mut check) }
+    free(check.page_use as *mut libc::c_void);
+    free(check.leaves as *mut libc::c_void);
+    free(check.next_leaves as *mut libc::c_void);
+    result?;
+    return Ok(check.output);
+}
+
+pub(crate) unsafe fn check_leaf_chain(mut check: *mut IntegrityCheck) {
+    /* The chain has to follow the leaves of a tree from left to right */
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < (*check).num_leaves {
+        let mut leaf: uint32_t = *(*check).leaves.offset(i as isize);
+        let mut expected: uint32_t = 0 as libc::c_int as uint32_t;
+        if i.wrapping_add(1 as libc::c_int as libc::c_uint) < (*check).num_leaves
+           {
+            expected =
+                *(*check).leaves.offset(i.wrapping_add(1 as libc::c_int as
+                                                        libc::c_uint) as
+                                         isize);
+            if expected == 0 as libc::c_int as libc::c_uint {
+                /* Followed by an unreadable subtree */
+                leaf = 0 as libc::c_int as uint32_t
+            }
+        }
+        if leaf != 0 as libc::c_int as libc::c_uint &&
+               *(*check).next_leaves.offset(i as isize) != expected {
+            writeln!((*check).output,
+                     "Page {}: next leaf is {}, expected {}", leaf,
+                     *(*check).next_leaves.offset(i as isize), expected).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1)
+        }
+        i = i.wrapping_add(1)
+    };
+}
+
+pub(crate) unsafe fn check_integrity_report(mut check: *mut IntegrityCheck)
+ -> Result<(), Error> {
+    /* The rest of .check, once the trees have been walked */
+    let mut pager: *mut Pager = (*check).pager;
+    check_freelist(check)?;
+    let mut page_num: uint32_t = 1 as libc::c_int as uint32_t;
+    while page_num < (*pager).num_pages {
+        if *(*check).page_use.offset(page_num as isize) == PAGE_USE_NONE {
+            writeln!((*check).output,
+                     "Page {}: neither in the tree nor on the free list",
+                     page_num).ok();
+            (*check).num_problems = (*check).num_problems.wrapping_add(1)
+        }
+        page_num = page_num.wrapping_add(1)
+    }
+    if (*check).num_problems == 0 as libc::c_int as libc::c_uint {
+        writeln!((*check).output, "ok").ok();
+    } else if (*check).num_problems == 1 as libc::c_int as libc::c_uint {
+        writeln!((*check).output, "1 problem found.").ok();
+    } else {
+        writeln!((*check).output, "{} problems found.",
+                 (*check).num_problems).ok();
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn create_new_root(mut table: *mut Table,
+                                         mut right_child_page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Handle splitting the root.
+  Old root copied to new page, becomes left child.
+  Address of right child passed in.
+  Re-initialize root page to contain the new root node.
+  New root node points to two children.
+  */
+    let mut root: *mut libc::c_void =
+        get_node((*table).pager, (*table).root_page_num)?;
+    let mut right_child: *mut libc::c_void =
+        get_node((*table).pager, right_child_page_num)?;
+    let mut left_child_page_num: uint32_t =
+        get_unused_page_num((*table).pager)?;
+    let mut left_child: *mut libc::c_void =
+        get_page((*table).pager, left_child_page_num)?;
+    pager_mark_dirty((*table).pager, (*table).root_page_num)?;
+    pager_mark_dirty((*table).pager, right_child_page_num)?;
+    pager_mark_dirty((*table).pager, left_child_page_num)?;
+    /* Left child has data copied from old root */
+    memcpy(left_child, root, (*(*table).pager).page_size as libc::c_ulong);
+    set_node_root(left_child, 0 as libc::c_int != 0);
+    if get_node_type(left_child) as libc::c_uint ==
+           NODE_INTERNAL as libc::c_int as libc::c_uint {
+        /* Children of the old root now hang off the left child */
+        let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+        while i <= *internal_node_num_keys(left_child) {
+            let mut child_page_num: uint32_t =
+                *internal_node_child(left_child, i);
+            let mut child: *mut libc::c_void =
+                get_node((*table).pager, child_page_num)?;
+            pager_mark_dirty((*table).pager, child_page_num)?;
+            *node_parent(child) = left_child_page_num;
+            i = i.wrapping_add(1)
+        }
+    }
+    /* Root node is a new internal node with one key and two children */
+    initialize_internal_node(root);
+    set_node_root(root, 1 as libc::c_int != 0);
+    *internal_node_num_keys(root) = 1 as libc::c_int as uint32_t;
+    *internal_node_child(root, 0 as libc::c_int as uint32_t) =
+        left_child_page_num;
+    let mut left_child_max_key: uint32_t =
+        get_node_max_key((*table).pager, left_child_page_num)?;
+    *internal_node_key(root, 0 as libc::c_int as uint32_t) =
+        left_child_max_key;
+    *internal_node_right_child(root) = right_child_page_num;
+    *node_parent(left_child) = (*table).root_page_num;
+    *node_parent(right_child) = (*table).root_page_num;
+    return Ok(());
+}
+
+pub(crate) unsafe fn internal_node_insert(mut table: *mut Table,
+                                              mut parent_page_num: uint32_t,
+                                              mut child_page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Add a new child/key pair to parent that corresponds to child
+  */
+    let mut parent: *mut libc::c_void =
+        get_node((*table).pager, parent_page_num)?;
+    let mut child: *mut libc::c_void =
+        get_node((*table).pager, child_page_num)?;
+    let mut child_max_key: uint32_t =
+        get_node_max_key((*table).pager, child_page_num)?;
+    let mut index: uint32_t = internal_node_find_child(parent, child_max_key);
+    let mut original_num_keys: uint32_t = *internal_node_num_keys(parent);
+    if original_num_keys >= (*(*table).pager).internal_node_max_cells {
+        internal_node_split_and_insert(table, parent_page_num,
+                                       child_page_num)?;
+        return Ok(())
+    }
+    pager_mark_dirty((*table).pager, parent_page_num)?;
+    *internal_node_num_keys(parent) =
+        original_num_keys.wrapping_add(1 as libc::c_int as libc::c_uint);
+    let mut right_child_page_num: uint32_t =
+        *internal_node_right_child(parent);
+    let mut right_child: *mut libc::c_void =
+        get_node((*table).pager, right_child_page_num)?;
+    let mut right_child_max_key: uint32_t =
+        get_node_max_key((*table).pager, right_child_page_num)?;
+    if child_max_key > right_child_max_key {
+        /* Replace right child */
+        *internal_node_child(parent, original_num_keys) =
+            right_child_page_num;
+        *internal_node_key(parent, original_num_keys) = right_child_max_key;
+        *internal_node_right_child(parent) = child_page_num
+    } else {
+        /* Make room for the new cell */
+        let mut i: uint32_t = original_num_keys;
+        while i > index {
+            let mut destination: *mut libc::c_void =
+                internal_node_cell(parent, i) as *mut libc::c_void;
+            let mut source: *mut libc::c_void =
+                internal_node_cell(parent,
+                                   i.wrapping_sub(1 as libc::c_int as
+                                                      libc::c_uint)) as
+                    *mut libc::c_void;
+            memcpy(destination, source,
+                   INTERNAL_NODE_CELL_SIZE as libc::c_ulong);
+            i = i.wrapping_sub(1)
+        }
+        *internal_node_child(parent, index) = child_page_num;
+        *internal_node_key(parent, index) = child_max_key
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn internal_node_split_and_insert(mut table: *mut Table,
+                                             mut old_page_num: uint32_t,
+                                             mut child_page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Create a new internal node and move the upper half of the
+  children over, together with the new child.
+  Update parent or create a new parent, which may split in turn.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut old_node: *mut libc::c_void = get_node(pager, old_page_num)?;
+    let mut old_max: uint32_t = get_node_max_key(pager, old_page_num)?;
+    let mut child: *mut libc::c_void = get_node(pager, child_page_num)?;
+    let mut child_max_key: uint32_t =
+        get_node_max_key(pager, child_page_num)?;
+    let mut new_page_num: uint32_t = get_unused_page_num(pager)?;
+    let mut new_node: *mut libc::c_void = get_page(pager, new_page_num)?;
+    pager_mark_dirty(pager, old_page_num)?;
+    pager_mark_dirty(pager, new_page_num)?;
+    initialize_internal_node(new_node);
+    /*
+  Gather every child of the full node plus the new one,
+  ordered by the maximum key of each subtree.
+  */
+    let mut num_keys: uint32_t = *internal_node_num_keys(old_node);
+    let mut num_children: uint32_t =
+        num_keys.wrapping_add(2 as libc::c_int as libc::c_uint);
+    let mut children: Vec<uint32_t> = vec![0; num_children as usize];
+    let mut keys: Vec<uint32_t> = vec![0; num_children as usize];
+    let mut j: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut inserted: bool = 0 as libc::c_int != 0;
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i <= num_keys {
+        let mut key: uint32_t = 0;
+        if i < num_keys {
+            key = *internal_node_key(old_node, i)
+        } else {
+            key =
+                get_node_max_key(pager,
+                                 *internal_node_right_child(old_node))?
+        }
+        if !inserted && child_max_key < key {
+            children[j as usize] = child_page_num;
+            keys[j as usize] = child_max_key;
+            j = j.wrapping_add(1);
+            inserted = 1 as libc::c_int != 0
+        }
+        children[j as usize] = *internal_node_child(old_node, i);
+        keys[j as usize] = key;
+        j = j.wrapping_add(1);
+        i = i.wrapping_add(1)
+    }
+    if !inserted {
+        children[j as usize] = child_page_num;
+        keys[j as usize] = child_max_key
+    }
+    /* Left half stays in the old node, right half goes to the new node */
+    let mut left_count: uint32_t =
+        num_children.wrapping_div(2 as libc::c_int as libc::c_uint);
+    let mut right_count: uint32_t = num_children.wrapping_sub(left_count);
+    *internal_node_num_keys(old_node) =
+        left_count.wrapping_sub(1 as libc::c_int as libc::c_uint);
+    *internal_node_num_keys(new_node) =
+        right_count.wrapping_sub(1 as libc::c_int as libc::c_uint);
+    i = 0 as libc::c_int as uint32_t;
+    while i < num_children {
+        let mut node: *mut libc::c_void = old_node;
+        let mut node_page_num: uint32_t = old_page_num;
+        let mut index_within_node: uint32_t = i;
+        if i >= left_count {
+            node = new_node;
+            node_page_num = new_page_num;
+            index_within_node = i.wrapping_sub(left_count)
+        }
+        *internal_node_child(node, index_within_node) =
+            children[i as usize];
+        if index_within_node < *internal_node_num_keys(node) {
+            *internal_node_key(node, index_within_node) =
+                keys[i as usize]
+        }
+        *node_parent(get_node(pager, children[i as usize])?) =
+            node_page_num;
+        pager_mark_dirty(pager, children[i as usize])?;
+        i = i.wrapping_add(1)
+    }
+    if is_node_root(old_node) {
+        return create_new_root(table, new_page_num)
+    } else {
+        let mut parent_page_num: uint32_t = *node_parent(old_node);
+        let mut new_max: uint32_t = get_node_max_key(pager, old_page_num)?;
+        let mut parent: *mut libc::c_void = get_node(pager, parent_page_num)?;
+        pager_mark_dirty(pager, parent_page_num)?;
+        *node_parent(new_node) = parent_page_num;
+        update_internal_node_key(parent, old_max, new_max);
+        return internal_node_insert(table, parent_page_num, new_page_num)
+    };
+}
+
+pub(crate) unsafe fn update_internal_node_key(mut node: *mut libc::c_void,
+                                                  mut old_key: uint32_t,
+                                                  mut new_key: uint32_t) {
+    let mut old_child_index: uint32_t =
+        internal_node_find_child(node, old_key);
+    /* The right child has no key of its own */
+    if old_child_index < *internal_node_num_keys(node) {
+        *internal_node_key(node, old_child_index) = new_key
+    };
+}
+
+pub(crate) unsafe fn leaf_node_build_cell(mut pager: *mut Pager, mut key: uint32_t,
+                                   mut record: &[u8])
+ -> Result<Vec<u8>, Error> {
+    /* The cell for a record, whatever does not fit goes to overflow pages */
+    let mut payload_size: uint32_t = record.len() as uint32_t;
+    let mut local_size: usize = payload_local_size(pager, payload_size) as usize;
+    let mut cell: Vec<u8> = vec![0; cell_size(pager, payload_size) as usize];
+    let mut cell_data: *mut libc::c_void =
+        cell.as_mut_ptr() as *mut libc::c_void;
+    *(cell_data.offset(LEAF_NODE_KEY_OFFSET as isize) as *mut uint32_t) = key;
+    *(cell_data.offset(LEAF_NODE_PAYLOAD_SIZE_OFFSET as isize) as
+          *mut uint32_t) = payload_size;
+    let mut offset: uint32_t = LEAF_NODE_CELL_HEADER_SIZE;
+    if payload_spills(pager, payload_size) {
+        *(cell_data.offset(offset as isize) as *mut uint32_t) =
+            overflow_write(pager, &record[local_size..])?;
+        offset = offset.wrapping_add(LEAF_NODE_OVERFLOW_SIZE)
+    }
+    memcpy(cell_data.offset(offset as isize),
+           record.as_ptr() as *const libc::c_void,
+           local_size as libc::c_ulong);
+    return Ok(cell);
+}
+
+pub(crate) unsafe fn leaf_node_cells(mut pager: *mut Pager,
+                              mut node: *mut libc::c_void) -> Vec<Vec<u8>> {
+    /* Copies of the cells of a leaf, in key order */
+    let mut cells: Vec<Vec<u8>> = Vec::new();
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < *leaf_node_num_cells(node) {
+        cells.push(::std::slice::from_raw_parts(leaf_node_cell(node, i) as
+                                                    *const u8,
+                                                leaf_node_cell_size(pager, node, i)
+                                                    as usize).to_vec());
+        i = i.wrapping_add(1)
+    }
+    return cells;
+}
+
+pub(crate) unsafe fn leaf_node_set_cells(mut pager: *mut Pager,
+                                  mut node: *mut libc::c_void,
+                                  mut cells: &[Vec<u8>]) {
+    /* Replace the cells of a leaf, packed at the end of the page */
+    let mut offset: uint32_t = (*pager).usable_size;
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < cells.len() {
+        offset = offset.wrapping_sub(cells[i].len() as uint32_t);
+        memcpy(node.offset(offset as isize),
+               cells[i].as_ptr() as *const libc::c_void,
+               cells[i].len() as libc::c_ulong);
+        *leaf_node_cell_pointer(node, i as uint32_t) = offset as uint16_t;
+        i = i.wrapping_add(1)
+    }
+    *leaf_node_num_cells(node) = cells.len() as uint32_t;
+    *leaf_node_content_offset(node) = offset;
+}
+
+pub(crate) unsafe fn cells_size(mut cells: &[Vec<u8>]) -> uint32_t {
+    /* Bytes the cells take on a leaf, their pointers included */
+    let mut size: uint32_t = 0 as libc::c_int as uint32_t;
+    for cell in cells.iter() {
+        size =
+            size.wrapping_add(cell.len() as
+                                  uint32_t).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE)
+    }
+    return size;
+}
+
+pub(crate) unsafe fn cells_split_point(mut pager: *mut Pager,
+                                mut cells: &[Vec<u8>]) -> usize {
+    /*
+  Where to divide cells that do not fit one leaf between two,
+  so both fit and take about as many bytes. Any two cells fit
+  a leaf, so there always is such a point.
+  */
+    let mut total: uint32_t = cells_size(cells);
+    let mut left: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut split: usize = 1 as libc::c_int as usize;
+    let mut best: uint32_t = 0xffffffff as libc::c_uint;
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i.wrapping_add(1) < cells.len() {
+        left =
+            left.wrapping_add(cells[i].len() as
+                                  uint32_t).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE);
+        let mut right: uint32_t = total.wrapping_sub(left);
+        let mut difference: uint32_t =
+            if left > right {
+                left.wrapping_sub(right)
+            } else { right.wrapping_sub(left) };
+        if left <= (*pager).leaf_node_space_for_cells &&
+               right <= (*pager).leaf_node_space_for_cells && difference < best {
+            split = i.wrapping_add(1);
+            best = difference
+        }
+        i = i.wrapping_add(1)
+    }
+    return split;
+}
+
+pub(crate) unsafe fn leaf_node_insert_cell(mut node: *mut libc::c_void,
+                                    mut cell_num: uint32_t,
+                                    mut cell: &[u8]) {
+    /* The caller made sure the cell fits in the free space */
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    let mut offset: uint32_t =
+        (*leaf_node_content_offset(node)).wrapping_sub(cell.len() as
+                                                           uint32_t);
+    memcpy(node.offset(offset as isize), cell.as_ptr() as *const libc::c_void,
+           cell.len() as libc::c_ulong);
+    memmove(leaf_node_cell_pointer(node,
+                                   cell_num.wrapping_add(1 as libc::c_int as
+                                                             libc::c_uint)) as
+                *mut libc::c_void,
+            leaf_node_cell_pointer(node, cell_num) as *const libc::c_void,
+            num_cells.wrapping_sub(cell_num).wrapping_mul(LEAF_NODE_CELL_POINTER_SIZE)
+                as libc::c_ulong);
+    *leaf_node_cell_pointer(node, cell_num) = offset as uint16_t;
+    *leaf_node_num_cells(node) =
+        num_cells.wrapping_add(1 as libc::c_int as libc::c_uint);
+    *leaf_node_content_offset(node) = offset;
+}
+
+pub(crate) unsafe fn leaf_node_remove_cell(mut pager: *mut Pager,
+                                    mut node: *mut libc::c_void,
+                                    mut cell_num: uint32_t) {
+    /*
+  Close the gap the cell leaves by moving the cells below it up,
+  so the free space stays in one piece between pointers and cells.
+  */
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    let mut offset: uint32_t =
+        *leaf_node_cell_pointer(node, cell_num) as uint32_t;
+    let mut size: uint32_t = leaf_node_cell_size(pager, node, cell_num);
+    let mut content_offset: uint32_t = *leaf_node_content_offset(node);
+    memmove(node.offset(content_offset.wrapping_add(size) as isize),
+            node.offset(content_offset as isize),
+            offset.wrapping_sub(content_offset) as libc::c_ulong);
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < num_cells {
+        if (*leaf_node_cell_pointer(node, i) as uint32_t) < offset {
+            *leaf_node_cell_pointer(node, i) =
+                (*leaf_node_cell_pointer(node, i) as
+                     uint32_t).wrapping_add(size) as uint16_t
+        }
+        i = i.wrapping_add(1)
+    }
+    memmove(leaf_node_cell_pointer(node, cell_num) as *mut libc::c_void,
+            leaf_node_cell_pointer(node,
+                                   cell_num.wrapping_add(1 as libc::c_int as
+                                                             libc::c_uint)) as
+                *const libc::c_void,
+            num_cells.wrapping_sub(cell_num).wrapping_sub(1 as libc::c_int as
+                                                              libc::c_uint).wrapping_mul(LEAF_NODE_CELL_POINTER_SIZE)
+                as libc::c_ulong);
+    *leaf_node_num_cells(node) =
+        num_cells.wrapping_sub(1 as libc::c_int as libc::c_uint);
+    *leaf_node_content_offset(node) = content_offset.wrapping_add(size);
+}
+
+pub(crate) unsafe fn leaf_node_split_and_insert(mut cursor: *mut Cursor,
+                                                    mut cell: Vec<u8>)
+ -> Result<(), Error> {
+    /*
+  Create a new node and move about half the bytes over.
+  Insert the new cell in one of the two nodes.
+  Update parent or create a new parent.
+  */
+    let mut old_node: *mut libc::c_void =
+        get_node((*(*cursor).table).pager, (*cursor).page_num)?;
+    let mut old_max: uint32_t =
+        get_node_max_key((*(*cursor).table).pager, (*cursor).page_num)?;
+    let mut new_page_num: uint32_t =
+        get_unused_page_num((*(*cursor).table).pager)?;
+    let mut new_node: *mut libc::c_void =
+        get_page((*(*cursor).table).pager, new_page_num)?;
+    pager_mark_dirty((*(*cursor).table).pager, (*cursor).page_num)?;
+    pager_mark_dirty((*(*cursor).table).pager, new_page_num)?;
+    initialize_leaf_node((*(*cursor).table).pager, new_node);
+    *node_parent(new_node) = *node_parent(old_node);
+    *leaf_node_next_leaf(new_node) = *leaf_node_next_leaf(old_node);
+    *leaf_node_next_leaf(old_node) = new_page_num;
+    /*
+  All existing cells plus the new one are divided between
+  old (left) and new (right) nodes by size, not by count.
+  */
+    let mut cells: Vec<Vec<u8>> =
+        leaf_node_cells((*(*cursor).table).pager, old_node);
+    cells.insert((*cursor).cell_num as usize, cell);
+    let mut split: usize = cells_split_point((*(*cursor).table).pager, &cells);
+    leaf_node_set_cells((*(*cursor).table).pager, old_node, &cells[..split]);
+    leaf_node_set_cells((*(*cursor).table).pager, new_node, &cells[split..]);
+    if is_node_root(old_node) {
+        return create_new_root((*cursor).table, new_page_num)
+    } else {
+        let mut parent_page_num: uint32_t = *node_parent(old_node);
+        let mut new_max: uint32_t =
+            get_node_max_key((*(*cursor).table).pager, (*cursor).page_num)?;
+        let mut parent: *mut libc::c_void =
+            get_node((*(*cursor).table).pager, parent_page_num)?;
+        pager_mark_dirty((*(*cursor).table).pager, parent_page_num)?;
+        update_internal_node_key(parent, old_max, new_max);
+        return internal_node_insert((*cursor).table, parent_page_num, new_page_num)
+    };
+}
+
+pub(crate) unsafe fn leaf_node_insert(mut cursor: *mut Cursor,
+                                          mut key: uint32_t,
+                                          mut value: *mut Row)
+ -> Result<(), Error> {
+    let mut record: Vec<u8> = serialize_row((*(*cursor).table).schema, value);
+    let mut cell: Vec<u8> =
+        leaf_node_build_cell((*(*cursor).table).pager, key, &record)?;
+    return leaf_node_place_cell(cursor, cell);
+}
+
+pub(crate) unsafe fn leaf_node_place_cell(mut cursor: *mut Cursor,
+                                   mut cell: Vec<u8>) -> Result<(), Error> {
+    let mut node: *mut libc::c_void =
+        get_page((*(*cursor).table).pager, (*cursor).page_num)?;
+    if (cell.len() as
+            uint32_t).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE) >
+           leaf_node_free_space((*(*cursor).table).pager, node) {
+        // Node full
+        return leaf_node_split_and_insert(cursor, cell)
+    }
+    pager_mark_dirty((*(*cursor).table).pager, (*cursor).page_num)?;
+    leaf_node_insert_cell(node, (*cursor).cell_num, &cell);
+    return Ok(());
+}
+
+pub(crate) unsafe fn leaf_node_replace(mut cursor: *mut Cursor, mut value: *mut Row)
+ -> Result<(), Error> {
+    /*
+  Put a changed row in place of the one under the cursor. The key
+  stays the same so the cell keeps its position, but it may grow
+  enough to split the leaf or shrink enough to leave it underfull.
+  */
+    let mut table: *mut Table = (*cursor).table;
+    let mut pager: *mut Pager = (*table).pager;
+    let mut node: *mut libc::c_void = get_page(pager, (*cursor).page_num)?;
+    let mut key: uint32_t = *leaf_node_key(node, (*cursor).cell_num);
+    let mut payload_size: uint32_t =
+        *leaf_node_payload_size(node, (*cursor).cell_num);
+    if payload_spills(pager, payload_size) {
+        overflow_free(pager, (*cursor).page_num,
+                      *leaf_node_overflow(node, (*cursor).cell_num),
+                      payload_size)?;
+    }
+    let mut record: Vec<u8> = serialize_row((*table).schema, value);
+    let mut cell: Vec<u8> = leaf_node_build_cell(pager, key, &record)?;
+    pager_mark_dirty(pager, (*cursor).page_num)?;
+    leaf_node_remove_cell(pager, node, (*cursor).cell_num);
+    if (cell.len() as uint32_t).wrapping_add(LEAF_NODE_CELL_POINTER_SIZE) >
+           leaf_node_free_space(pager, node) {
+        return leaf_node_split_and_insert(cursor, cell)
+    }
+    leaf_node_insert_cell(node, (*cursor).cell_num, &cell);
+    if !is_node_root(node) &&
+           leaf_node_used_space(pager, node) < (*pager).leaf_node_min_fill {
+        return leaf_node_rebalance(table, (*cursor).page_num)
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn internal_node_child_index(mut node: *mut libc::c_void,
+                                       mut child_page_num: uint32_t)
+ -> Result<uint32_t, Error> {
+    /*
+  Return the position of the given child page within node. A
+  child its parent does not list has a wrong parent pointer.
+  */
+    let mut num_keys: uint32_t = *internal_node_num_keys(node);
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i <= num_keys {
+        if *internal_node_child(node, i) == child_page_num { return Ok(i) }
+        i = i.wrapping_add(1)
+    }
+    return Err(Error::Corrupt(child_page_num));
+}
+
+pub(crate) unsafe fn internal_node_remove_cell(mut node: *mut libc::c_void,
+                                       mut cell_num: uint32_t) {
+    /*
+  Remove a child/key pair, the right child is left untouched
+  */
+    let mut num_keys: uint32_t = *internal_node_num_keys(node);
+    let mut i: uint32_t = cell_num;
+    while i.wrapping_add(1 as libc::c_int as libc::c_uint) < num_keys {
+        memcpy(internal_node_cell(node, i) as *mut libc::c_void,
+               internal_node_cell(node,
+                                  i.wrapping_add(1 as libc::c_int as
+                                                     libc::c_uint)) as
+                   *const libc::c_void,
+               INTERNAL_NODE_CELL_SIZE as libc::c_ulong);
+        i = i.wrapping_add(1)
+    }
+    *internal_node_num_keys(node) =
+        num_keys.wrapping_sub(1 as libc::c_int as libc::c_uint);
+}
+
+pub(crate) unsafe fn collapse_root(mut table: *mut Table)
+ -> Result<(), Error> {
+    /*
+  Root is an internal node left with a single child.
+  Pull that child up into the root page so the tree loses a level.
+  */
+    let mut root: *mut libc::c_void =
+        get_node((*table).pager, (*table).root_page_num)?;
+    let mut child_page_num: uint32_t = *internal_node_right_child(root);
+    let mut child: *mut libc::c_void =
+        get_node((*table).pager, child_page_num)?;
+    pager_mark_dirty((*table).pager, (*table).root_page_num)?;
+    memcpy(root, child, (*(*table).pager).page_size as libc::c_ulong);
+    set_node_root(root, 1 as libc::c_int != 0);
+    if get_node_type(root) as libc::c_uint ==
+           NODE_INTERNAL as libc::c_int as libc::c_uint {
+        let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+        while i <= *internal_node_num_keys(root) {
+            let mut grandchild_page_num: uint32_t =
+                *internal_node_child(root, i);
+            let mut grandchild: *mut libc::c_void =
+                get_node((*table).pager, grandchild_page_num)?;
+            pager_mark_dirty((*table).pager, grandchild_page_num)?;
+            *node_parent(grandchild) = (*table).root_page_num;
+            i = i.wrapping_add(1)
+        }
+    }
+    free_page((*table).pager, child_page_num)?;
+    return Ok(());
+}
+
+pub(crate) unsafe fn internal_node_merge(mut table: *mut Table,
+                                 mut parent_page_num: uint32_t,
+                                 mut left_index: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Merge the children at left_index and left_index + 1.
+  The separator between them comes down from the parent.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut parent: *mut libc::c_void = get_node(pager, parent_page_num)?;
+    let mut left_page_num: uint32_t = *internal_node_child(parent, left_index);
+    let mut right_page_num: uint32_t =
+        *internal_node_child(parent,
+                             left_index.wrapping_add(1 as libc::c_int as
+                                                         libc::c_uint));
+    let mut left: *mut libc::c_void = get_node(pager, left_page_num)?;
+    let mut right: *mut libc::c_void = get_node(pager, right_page_num)?;
+    pager_mark_dirty(pager, parent_page_num)?;
+    pager_mark_dirty(pager, left_page_num)?;
+    let mut left_num_keys: uint32_t = *internal_node_num_keys(left);
+    let mut right_num_keys: uint32_t = *internal_node_num_keys(right);
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i <= right_num_keys {
+        let mut child_page_num: uint32_t = *internal_node_child(right, i);
+        let mut child: *mut libc::c_void = get_node(pager, child_page_num)?;
+        pager_mark_dirty(pager, child_page_num)?;
+        *node_parent(child) = left_page_num;
+        i = i.wrapping_add(1)
+    }
+    let mut old_right_child: uint32_t = *internal_node_right_child(left);
+    *internal_node_num_keys(left) =
+        left_num_keys.wrapping_add(1 as libc::c_int as
+                                       libc::c_uint).wrapping_add(right_num_keys);
+    *internal_node_child(left, left_num_keys) = old_right_child;
+    *internal_node_key(left, left_num_keys) =
+        *internal_node_key(parent, left_index);
+    memcpy(internal_node_cell(left,
+                              left_num_keys.wrapping_add(1 as libc::c_int as
+                                                             libc::c_uint)) as
+               *mut libc::c_void,
+           internal_node_cell(right, 0 as libc::c_int as uint32_t) as
+               *const libc::c_void,
+           right_num_keys.wrapping_mul(INTERNAL_NODE_CELL_SIZE) as
+               libc::c_ulong);
+    *internal_node_right_child(left) = *internal_node_right_child(right);
+    free_page(pager, right_page_num)?;
+    /* Merged node takes over the slot of the right sibling */
+    *internal_node_child(parent,
+                         left_index.wrapping_add(1 as libc::c_int as
+                                                     libc::c_uint)) =
+        left_page_num;
+    internal_node_remove_cell(parent, left_index);
+    internal_node_rebalance(table, parent_page_num)?;
+    return Ok(());
+}
+
+pub(crate) unsafe fn internal_node_rebalance(mut table: *mut Table,
+                                     mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Restore the minimum fill of an internal node after it lost a child,
+  borrowing from a sibling when one can spare a child
+  and merging with one otherwise.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut node: *mut libc::c_void = get_node(pager, page_num)?;
+    let mut num_keys: uint32_t = *internal_node_num_keys(node);
+    if is_node_root(node) {
+        if num_keys == 0 as libc::c_int as libc::c_uint {
+            collapse_root(table)?;
+        }
+        return Ok(())
+    }
+    if num_keys >= (*pager).internal_node_min_cells { return Ok(()) }
+    let mut parent_page_num: uint32_t = *node_parent(node);
+    let mut parent: *mut libc::c_void = get_node(pager, parent_page_num)?;
+    let mut index: uint32_t = internal_node_child_index(parent, page_num)?;
+    if index > 0 as libc::c_int as libc::c_uint {
+        let mut left_page_num: uint32_t =
+            *internal_node_child(parent,
+                                 index.wrapping_sub(1 as libc::c_int as
+                                                        libc::c_uint));
+        let mut left: *mut libc::c_void = get_node(pager, left_page_num)?;
+        let mut left_num_keys: uint32_t = *internal_node_num_keys(left);
+        if left_num_keys > (*pager).internal_node_min_cells {
+            /* Move the right child of the left sibling over */
+            pager_mark_dirty(pager, page_num)?;
+            pager_mark_dirty(pager, parent_page_num)?;
+            pager_mark_dirty(pager, left_page_num)?;
+            let mut i: uint32_t = num_keys;
+            while i > 0 as libc::c_int as libc::c_uint {
+                memcpy(internal_node_cell(node, i) as *mut libc::c_void,
+                       internal_node_cell(node,
+                                          i.wrapping_sub(1 as libc::c_int as
+                                                             libc::c_uint)) as
+                           *const libc::c_void,
+                       INTERNAL_NODE_CELL_SIZE as libc::c_ulong);
+                i = i.wrapping_sub(1)
+            }
+            *internal_node_num_keys(node) =
+                num_keys.wrapping_add(1 as libc::c_int as libc::c_uint);
+            let mut moved_page_num: uint32_t =
+                *internal_node_right_child(left);
+            *internal_node_child(node, 0 as libc::c_int as uint32_t) =
+                moved_page_num;
+            *internal_node_key(node, 0 as libc::c_int as uint32_t) =
+                *internal_node_key(parent,
+                                   index.wrapping_sub(1 as libc::c_int as
+                                                          libc::c_uint));
+            *node_parent(get_node(pager, moved_page_num)?) = page_num;
+            pager_mark_dirty(pager, moved_page_num)?;
+            let mut last: uint32_t =
+                left_num_keys.wrapping_sub(1 as libc::c_int as libc::c_uint);
+            *internal_node_right_child(left) = *internal_node_child(left, last);
+            *internal_node_key(parent,
+                               index.wrapping_sub(1 as libc::c_int as
+                                                      libc::c_uint)) =
+                *internal_node_key(left, last);
+            *internal_node_num_keys(left) = last;
+            return Ok(())
+        }
+    }
+    if index < *internal_node_num_keys(parent) {
+        let mut right_page_num: uint32_t =
+            *internal_node_child(parent,
+                                 index.wrapping_add(1 as libc::c_int as
+                                                        libc::c_uint));
+        let mut right: *mut libc::c_void = get_node(pager, right_page_num)?;
+        if *internal_node_num_keys(right) > (*pager).internal_node_min_cells {
+            /* Move the first child of the right sibling over */
+            pager_mark_dirty(pager, page_num)?;
+            pager_mark_dirty(pager, parent_page_num)?;
+            pager_mark_dirty(pager, right_page_num)?;
+            let mut old_right_child: uint32_t =
+                *internal_node_right_child(node);
+            *internal_node_num_keys(node) =
+                num_keys.wrapping_add(1 as libc::c_int as libc::c_uint);
+            *internal_node_child(node, num_keys) = old_right_child;
+            *internal_node_key(node, num_keys) =
+                *internal_node_key(parent, index);
+            let mut moved_page_num: uint32_t =
+                *internal_node_child(right, 0 as libc::c_int as uint32_t);
+            *internal_node_right_child(node) = moved_page_num;
+            *node_parent(get_node(pager, moved_page_num)?) = page_num;
+            pager_mark_dirty(pager, moved_page_num)?;
+            *internal_node_key(parent, index) =
+                *internal_node_key(right, 0 as libc::c_int as uint32_t);
+            internal_node_remove_cell(right, 0 as libc::c_int as uint32_t);
+            return Ok(())
+        }
+    }
+    /* Neither sibling can spare a child */
+    if index > 0 as libc::c_int as libc::c_uint {
+        internal_node_merge(table, parent_page_num,
+                            index.wrapping_sub(1 as libc::c_int as
+                                                   libc::c_uint))?;
+    } else { internal_node_merge(table, parent_page_num, index)?; }
+    return Ok(());
+}
+
+pub(crate) unsafe fn leaf_node_merge(mut table: *mut Table,
+                             mut parent_page_num: uint32_t,
+                             mut left_index: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Append the right neighbour of the leaf at left_index to it.
+  Siblings under one parent are adjacent in the leaf chain.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut parent: *mut libc::c_void = get_node(pager, parent_page_num)?;
+    let mut left_page_num: uint32_t = *internal_node_child(parent, left_index);
+    let mut left: *mut libc::c_void = get_node(pager, left_page_num)?;
+    let mut right_page_num: uint32_t = *leaf_node_next_leaf(left);
+    let mut right: *mut libc::c_void = get_node(pager, right_page_num)?;
+    let mut cells: Vec<Vec<u8>> = leaf_node_cells(pager, left);
+    cells.extend(leaf_node_cells(pager, right));
+    pager_mark_dirty(pager, left_page_num)?;
+    pager_mark_dirty(pager, parent_page_num)?;
+    leaf_node_set_cells(pager, left, &cells);
+    *leaf_node_next_leaf(left) = *leaf_node_next_leaf(right);
+    free_page(pager, right_page_num)?;
+    /* Merged node takes over the slot of the right sibling */
+    *internal_node_child(parent,
+                         left_index.wrapping_add(1 as libc::c_int as
+                                                     libc::c_uint)) =
+        left_page_num;
+    internal_node_remove_cell(parent, left_index);
+    internal_node_rebalance(table, parent_page_num)?;
+    return Ok(());
+}
+
+pub(crate) unsafe fn leaf_node_rebalance(mut table: *mut Table,
+                                 mut page_num: uint32_t)
+ -> Result<(), Error> {
+    /*
+  Restore the minimum fill of a leaf after a delete, together
+  with its left sibling, or its right one when it has none:
+  merge the two when their cells fit one leaf and spread the
+  cells evenly over both otherwise.
+  */
+    let mut pager: *mut Pager = (*table).pager;
+    let mut node: *mut libc::c_void = get_node(pager, page_num)?;
+    let mut parent_page_num: uint32_t = *node_parent(node);
+    let mut parent: *mut libc::c_void = get_node(pager, parent_page_num)?;
+    let mut left_index: uint32_t =
+        internal_node_child_index(parent, page_num)?;
+    if left_index > 0 as libc::c_int as libc::c_uint {
+        left_index = left_index.wrapping_sub(1 as libc::c_int as libc::c_uint)
+    }
+    let mut left_page_num: uint32_t = *internal_node_child(parent, left_index);
+    let mut left: *mut libc::c_void = get_node(pager, left_page_num)?;
+    let mut right_page_num: uint32_t = *leaf_node_next_leaf(left);
+    let mut right: *mut libc::c_void = get_node(pager, right_page_num)?;
+    if leaf_node_used_space(pager,
+                            left).wrapping_add(leaf_node_used_space(pager,
+                                                                    right)) <=
+           (*pager).leaf_node_space_for_cells {
+        return leaf_node_merge(table, parent_page_num, left_index)
+    }
+    pager_mark_dirty(pager, left_page_num)?;
+    pager_mark_dirty(pager, right_page_num)?;
+    pager_mark_dirty(pager, parent_page_num)?;
+    let mut cells: Vec<Vec<u8>> = leaf_node_cells(pager, left);
+    cells.extend(leaf_node_cells(pager, right));
+    let mut split: usize = cells_split_point(pager, &cells);
+    leaf_node_set_cells(pager, left, &cells[..split]);
+    leaf_node_set_cells(pager, right, &cells[split..]);
+    *internal_node_key(parent, left_index) =
+        get_node_max_key(pager, left_page_num)?;
+    return Ok(());
+}
+
+pub(crate) unsafe fn leaf_node_delete(mut cursor: *mut Cursor)
+ -> Result<(), Error> {
+    let mut table: *mut Table = (*cursor).table;
+    let mut node: *mut libc::c_void =
+        get_node((*table).pager, (*cursor).page_num)?;
+    let mut old_max: uint32_t =
+        get_node_max_key((*table).pager, (*cursor).page_num)?;
+    let mut payload_size: uint32_t =
+        *leaf_node_payload_size(node, (*cursor).cell_num);
+    if payload_spills((*table).pager, payload_size) {
+        overflow_free((*table).pager, (*cursor).page_num,
+                      *leaf_node_overflow(node, (*cursor).cell_num),
+                      payload_size)?;
+    }
+    pager_mark_dirty((*table).pager, (*cursor).page_num)?;
+    leaf_node_remove_cell((*table).pager, node, (*cursor).cell_num);
+    let mut num_cells: uint32_t = *leaf_node_num_cells(node);
+    if is_node_root(node) { return Ok(()) }
+    if num_cells > 0 as libc::c_int as libc::c_uint &&
+           (*cursor).cell_num == num_cells {
+        /* The largest key went away, tighten the separator above it */
+        let mut parent: *mut libc::c_void =
+            get_node((*table).pager, *node_parent(node))?;
+        pager_mark_dirty((*table).pager, *node_parent(node))?;
+        update_internal_node_key(parent, old_max,
+                                 get_node_max_key((*table).pager,
+                                                  (*cursor).page_num)?);
+    }
+    if leaf_node_used_space((*table).pager, node) <
+           (*(*table).pager).leaf_node_min_fill {
+        leaf_node_rebalance(table, (*cursor).page_num)?;
+    }
+    return Ok(());
+}
+
+pub(crate) unsafe fn execute_insert(mut statement: *mut Statement,
+                                        mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut row_to_insert: *mut Row = &mut (*statement).row_to_insert;
+    let mut key_to_insert: uint32_t = row_key(row_to_insert);
+    let mut cursor: *mut Cursor = table_find(table, key_to_insert)?;
+    let mut result: Result<(), Error> =
+        match get_page((*table).pager, (*cursor).page_num) {
+            Ok(node) if
+            (*cursor).cell_num < *leaf_node_num_cells(node) &&
+                *leaf_node_key(node, (*cursor).cell_num) == key_to_insert
+            => {
+                Err(Error::DuplicateKey)
+            }
+            Ok(_) => {
+                leaf_node_insert(cursor, key_to_insert, row_to_insert)
+            }
+            Err(error) => { Err(error) }
+        };
+    free(cursor as *mut libc::c_void);
+    result?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_select(mut statement: *mut Statement,
+                                        mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut cursor: *mut Cursor = table_start(table)?;
+    let mut row: Row = Row{values: Vec::new(),};
+    let mut result: Result<(), Error> = Ok(());
+    while result.is_ok() && !(*cursor).end_of_table {
+        pager_release((*table).pager);
+        result = cursor_row(cursor, &mut row);
+        if result.is_err() { break ; }
+        (*statement).rows.push(row.clone());
+        result = cursor_advance(cursor)
+    }
+    free(cursor as *mut libc::c_void);
+    result?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_delete(mut statement: *mut Statement,
+                                        mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut num_deleted: uint32_t = 0 as libc::c_int as uint32_t;
+    let mut key: uint32_t = (*statement).min_id;
+    while key <= (*statement).max_id {
+        pager_release((*table).pager);
+        /* The tree may be reshaped by every delete, so search afresh */
+        let mut cursor: *mut Cursor = table_find(table, key)?;
+        let mut deleted: Result<Option<uint32_t>, Error> =
+            delete_next_in_range(statement, cursor);
+        free(cursor as *mut libc::c_void);
+        let mut found_key: uint32_t =
+            match deleted? { Some(found_key) => found_key, None => break , };
+        num_deleted = num_deleted.wrapping_add(1);
+        if found_key == (*statement).max_id { break ; }
+        key = found_key.wrapping_add(1 as libc::c_int as libc::c_uint)
+    }
+    if num_deleted == 0 as libc::c_int as libc::c_uint {
+        return Ok(ExecuteResult::NoRowsAffected)
+    }
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn delete_next_in_range(mut statement: *mut Statement,
+                                   mut cursor: *mut Cursor)
+ -> Result<Option<uint32_t>, Error> {
+    /* Delete the first row at or after the cursor, unless it is out of range */
+    let mut pager: *mut Pager = (*(*cursor).table).pager;
+    let mut node: *mut libc::c_void = get_node(pager, (*cursor).page_num)?;
+    if (*cursor).cell_num >= *leaf_node_num_cells(node) {
+        let mut next_page_num: uint32_t = *leaf_node_next_leaf(node);
+        if next_page_num == 0 as libc::c_int as libc::c_uint {
+            return Ok(None)
+        }
+        (*cursor).page_num = next_page_num;
+        (*cursor).cell_num = 0 as libc::c_int as uint32_t;
+        node = get_node(pager, next_page_num)?
+    }
+    let mut found_key: uint32_t = *leaf_node_key(node, (*cursor).cell_num);
+    if found_key > (*statement).max_id { return Ok(None) }
+    leaf_node_delete(cursor)?;
+    return Ok(Some(found_key));
+}
+
+pub(crate) unsafe fn execute_update(mut statement: *mut Statement,
+                                        mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut new_values: *mut Row = &mut (*statement).row_to_insert;
+    let mut key: uint32_t = row_key(new_values);
+    let mut cursor: *mut Cursor = table_find(table, key)?;
+    let mut result: Result<ExecuteResult, Error> =
+        update_row(statement, cursor);
+    free(cursor as *mut libc::c_void);
+    return result;
+}
+
+pub(crate) unsafe fn update_row(mut statement: *mut Statement,
+                         mut cursor: *mut Cursor)
+ -> Result<ExecuteResult, Error> {
+    let mut new_values: *mut Row = &mut (*statement).row_to_insert;
+    let mut node: *mut libc::c_void =
+        get_page((*(*cursor).table).pager, (*cursor).page_num)?;
+    if (*cursor).cell_num >= *leaf_node_num_cells(node) ||
+           *leaf_node_key(node, (*cursor).cell_num) != row_key(new_values) {
+        return Ok(ExecuteResult::NoRowsAffected)
+    }
+    let mut row: Row = Row{values: Vec::new(),};
+    cursor_row(cursor, &mut row)?;
+    let mut i: usize = 0 as libc::c_int as usize;
+    while i < row.values.len() {
+        if *(*statement).set_columns.as_ptr().offset(i as isize) {
+            row.values[i] =
+                (*(*new_values).values.as_ptr().offset(i as isize)).clone()
+        }
+        i = i.wrapping_add(1)
+    }
+    leaf_node_replace(cursor, &mut row)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_create_table(mut statement: *mut Statement,
+                                   mut catalog: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    /* Another connection may have been first, look again under the lock */
+    catalog_load(catalog)?;
+    let mut schema: *mut Schema = &mut (*statement).schema;
+    if !catalog_find(catalog,
+                     (*schema).name.as_ptr() as *const libc::c_char).is_null()
+       {
+        return Err(Error::TableExists)
+    }
+    let mut pager: *mut Pager = (*catalog).pager;
+    let mut root_page_num: uint32_t = get_unused_page_num(pager)?;
+    let mut root: *mut libc::c_void = get_page(pager, root_page_num)?;
+    pager_mark_dirty(pager, root_page_num)?;
+    initialize_leaf_node(pager, root);
+    set_node_root(root, 1 as libc::c_int != 0);
+    /* Catalog ids only grow, a new table gets the one after the last */
+    let mut id: uint32_t = 1 as libc::c_int as uint32_t;
+    let mut catalog_root: *mut libc::c_void =
+        get_page(pager, (*catalog).root_page_num)?;
+    if get_node_type(catalog_root) == NODE_INTERNAL ||
+           *leaf_node_num_cells(catalog_root) >
+               0 as libc::c_int as libc::c_uint {
+        id =
+            get_node_max_key(pager,
+                             (*catalog).root_page_num)?.wrapping_add(1 as libc::c_int as
+                                                             libc::c_uint)
+    }
+    let mut row: Row =
+        Row{values:
+                vec![Value::Integer(id as int64_t),
+                     Value::Text(schema_name(schema)),
+                     Value::Integer(root_page_num as int64_t),
+                     Value::Text((*schema).sql.clone())],};
+    let mut cursor: *mut Cursor = table_find(catalog, id)?;
+    let mut result: Result<(), Error> = leaf_node_insert(cursor, id, &mut row);
+    free(cursor as *mut libc::c_void);
+    result?;
+    (*catalog).tables.push(Box::into_raw(Box::new(Table{pager: pager,
+                                                        root_page_num:
+                                                            root_page_num,
+                                                        schema:
+                                                            Box::into_raw(Box::new(::std::mem::replace(&mut *schema,
+                                                                                                       new_schema()))),
+                                                        tables:
+                                                            Vec::new(),})));
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_pragma(mut statement: *mut Statement,
+                             mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut pager: *mut Pager = (*table).pager;
+    match (*statement).pragma {
+        PRAGMA_JOURNAL_MODE => {
+            if (*statement).pragma_set {
+                if (*pager).in_transaction {
+                    return Ok(ExecuteResult::TransactionActive)
+                }
+                pager_set_journal_mode(pager,
+                                       (*statement).pragma_journal_mode)?;
+            }
+            writeln!((*statement).output, "journal_mode = {}",
+                     journal_mode_name((*pager).journal_mode)).ok();
+        }
+        PRAGMA_SYNCHRONOUS => {
+            if (*statement).pragma_set {
+                (*pager).synchronous = (*statement).pragma_synchronous
+            }
+            writeln!((*statement).output, "synchronous = {}",
+                     synchronous_name((*pager).synchronous)).ok();
+        }
+        PRAGMA_BUSY_TIMEOUT => {
+            if (*statement).pragma_set {
+                (*pager).busy_timeout = (*statement).pragma_busy_timeout
+            }
+            writeln!((*statement).output, "busy_timeout = {}",
+                     (*pager).busy_timeout).ok();
+        }
+        PRAGMA_PAGE_SIZE => {
+            writeln!((*statement).output, "page_size = {}",
+                     (*pager).page_size).ok();
+        }
+        _ => { }
+    }
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_begin(mut statement: *mut Statement,
+                            mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    if (*(*table).pager).in_transaction {
+        return Ok(ExecuteResult::TransactionActive)
+    }
+    pager_begin((*table).pager)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_commit(mut statement: *mut Statement,
+                             mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    if !(*(*table).pager).in_transaction {
+        return Ok(ExecuteResult::NoTransaction)
+    }
+    pager_end((*table).pager)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_rollback(mut statement: *mut Statement,
+                               mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    if !(*(*table).pager).in_transaction {
+        return Ok(ExecuteResult::NoTransaction)
+    }
+    pager_rollback((*table).pager)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_savepoint(mut statement: *mut Statement,
+                                mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    pager_savepoint((*table).pager,
+                    (*statement).savepoint_name.as_mut_ptr())?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_release(mut statement: *mut Statement,
+                              mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut savepoint: *mut Savepoint =
+        pager_savepoint_find((*table).pager,
+                             (*statement).savepoint_name.as_mut_ptr());
+    if savepoint.is_null() { return Ok(ExecuteResult::NoSuchSavepoint) }
+    pager_savepoint_release((*table).pager, savepoint)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_rollback_to(mut statement: *mut Statement,
+                                  mut table: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    let mut savepoint: *mut Savepoint =
+        pager_savepoint_find((*table).pager,
+                             (*statement).savepoint_name.as_mut_ptr());
+    if savepoint.is_null() { return Ok(ExecuteResult::NoSuchSavepoint) }
+    pager_rollback_to((*table).pager, savepoint)?;
+    return Ok(ExecuteResult::Success);
+}
+
+pub(crate) unsafe fn execute_statement(mut statement: *mut Statement,
+                                           mut catalog: *mut Table)
+ -> Result<ExecuteResult, Error> {
+    match (*statement).type_0 {
+        STATEMENT_PRAGMA => { }
+        STATEMENT_INSERT | STATEMENT_DELETE | STATEMENT_UPDATE |
+        STATEMENT_CREATE_TABLE => {
+            pager_begin_write((*catalog).pager)?;
+        }
+        _ => {
+            pager_begin_read((*catalog).pager)?;
+        }
+    }
+    let mut table: *mut Table = catalog;
+    match (*statement).type_0 {
+        STATEMENT_INSERT | STATEMENT_SELECT | STATEMENT_DELETE |
+        STATEMENT_UPDATE => {
+            /* Named when prepared, found again now the lock is held */
+            catalog_load(catalog)?;
+            table =
+                catalog_find(catalog, (*statement).table_name.as_ptr() as
+                                          *const libc::c_char);
+            if table.is_null() {
+                return Err(Error::Syntax(PrepareResult::NoSuchTable))
+            }
+        }
+        _ => { }
+    }
+    match (*statement).type_0 {
+        STATEMENT_INSERT => { return execute_insert(statement, table) }
+        STATEMENT_SELECT => { return execute_select(statement, table) }
+        STATEMENT_DELETE => { return execute_delete(statement, table) }
+        STATEMENT_UPDATE => { return execute_update(statement, table) }
+        STATEMENT_PRAGMA => { return execute_pragma(statement, table) }
+        STATEMENT_BEGIN => { return execute_begin(statement, table) }
+        STATEMENT_COMMIT => { return execute_commit(statement, table) }
+        STATEMENT_ROLLBACK => { return execute_rollback(statement, table) }
+        STATEMENT_SAVEPOINT => { return execute_savepoint(statement, table) }
+        STATEMENT_RELEASE => { return execute_release(statement, table) }
+        STATEMENT_ROLLBACK_TO => {
+            return execute_rollback_to(statement, table)
+        }
+        STATEMENT_CREATE_TABLE => {
+            return execute_create_table(statement, table)
+        }
+    }
+}
+
+pub(crate) fn new_statement() -> Statement {
+    return Statement{type_0: STATEMENT_INSERT,
+                     row_to_insert: Row{values: Vec::new(),},
+                     table_name: [0; TABLE_NAME_SIZE + 1],
+                     min_id: 0,
+                     max_id: 0,
+                     set_columns: Vec::new(),
+                     schema: new_schema(),
+                     pragma: 0,
+                     pragma_journal_mode: JournalMode::Wal,
+                     pragma_synchronous: Synchronous::Full,
+                     pragma_busy_timeout: 0,
+                     pragma_set: false,
+                     savepoint_name: [0; 33],
+                     rows: Vec::new(),
+                     output: String::new(),};
+}
+
+/*
+ * Sharing a Database Between Threads
+ *   Every Connection has a pager of its own, with its own file
+ *   descriptor, locks and WAL read mark, so connections in one
+ *   process keep each other out the way processes do. insert, get
+ *   and scan borrow an idle pager while they run, and open another
+ *   one when none is idle. Nothing waits on a mutex for another
+ *   connection: a lock it holds is waited for up to the busy
+ *   timeout, after which the call fails with Error::Locked.
+ */
+pub(crate) struct IdlePagers {
+    /* Catalogs with their pagers, none of them in a transaction */
+    pub tables: Vec<*mut Table>,
+    /* What every pager handed out is set to */
+    pub journal_mode: JournalMode,
+    pub synchronous: Synchronous,
+}
+
+/* The tables are only reached with the mutex around them held */
+unsafe impl Send for IdlePagers { }
+
+/* More idle pagers than this are closed when they come back */
+const DATABASE_MAX_IDLE_PAGERS: usize = 4;
+
+pub struct Database {
+    filename: ::std::ffi::CString,
+    idle: ::std::sync::Mutex<IdlePagers>,
+}
+
+pub struct Connection {
+    database: ::std::sync::Arc<Database>,
+    /* Taken from the database by the first statement */
+    table: *mut Table,
+}
+
+/* The pager is only reached through &mut self */
+unsafe impl Send for Connection { }
+
+/*
+  Rows in key order, read through a pager of its own. The snapshot
+  it started on stays readable until it is dropped: its read mark
+  keeps checkpoints from overwriting the pages, and writers go on
+  meanwhile.
+  */
+pub struct Scan<'a> {
+    database: &'a Database,
+    table: *mut Table,
+    cursor: *mut Cursor,
+    /* The first error ends the scan */
+    result: Result<(), Error>,
+}
+
+impl Row {
+    pub fn new(values: Vec<Value>) -> Row {
+        /* Checked against the table's schema when it is inserted */
+        return Row{values: values,};
+    }
+
+    pub fn id(&self) -> Option<u32> {
+        /* The key is the first column, None if that cannot be one */
+        match self.values.first() {
+            Some(Value::Integer(id)) => {
+                return ::std::convert::TryFrom::try_from(*id).ok()
+            }
+            _ => { return None }
+        }
+    }
+}
+
+impl ::std::fmt::Display for Row {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        /* The way select prints a row */
+        let mut values: Vec<String> = Vec::new();
+        for value in self.values.iter() {
+            match value {
+                Value::Null => { values.push("null".to_string()) }
+                Value::Integer(integer) => {
+                    values.push(integer.to_string())
+                }
+                Value::Real(real) => { values.push(format!("{:?}", real)) }
+                Value::Text(text) => { values.push(text.clone()) }
+                Value::Blob(blob) => {
+                    let mut hex: Vec<String> =
+                        blob.iter().map(|byte|
+                                            format!("{:02x}", byte)).collect();
+                    values.push(format!("x'{}'", hex.concat()))
+                }
+                Value::Boolean(boolean) => {
+                    values.push(boolean.to_string())
+                }
+            }
+        }
+        write!(f, "({})", values.join(", "))
+    }
+}
+
+impl Database {
+    pub fn open(filename: &str) -> Result<Database, Error> {
+        return Database::open_with_page_size(filename, DEFAULT_PAGE_SIZE);
+    }
+
+    /* page_size is only used when the file does not exist yet */
+    pub fn open_with_page_size(filename: &str, page_size: u32)
+     -> Result<Database, Error> {
+        let filename =
+            ::std::ffi::CString::new(filename).map_err(|_| Error::NulByte)?;
+        unsafe {
+            let table: *mut Table = db_open(filename.as_ptr(), page_size)?;
+            let mut pager: *mut Pager = (*table).pager;
+            let idle =
+                IdlePagers{tables: vec![table],
+                           journal_mode: (*pager).journal_mode,
+                           synchronous: (*pager).synchronous,};
+            return Ok(Database{filename: filename,
+                               idle: ::std::sync::Mutex::new(idle),});
+        }
+    }
+
+    fn take(&self) -> Result<*mut Table, Error> {
+        /* An idle pager, or a new one, set up the way the database is */
+        let (table, journal_mode, synchronous) =
+            {
+                let mut idle = self.idle.lock().unwrap();
+                (idle.tables.pop(), idle.journal_mode, idle.synchronous)
+            };
+        unsafe {
+            let table: *mut Table =
+                match table {
+                    Some(table) => table,
+                    /* The file exists, so its own page size is used */
+                    None => {
+                        db_open(self.filename.as_ptr(), DEFAULT_PAGE_SIZE)?
+                    }
+                };
+            let mut pager: *mut Pager = (*table).pager;
+            (*pager).synchronous = synchronous;
+            if let Err(error) =
+                   pager_statement_finish(pager,
+                                          pager_set_journal_mode(pager,
+                                                                 journal_mode))
+                   {
+                self.give_back(table);
+                return Err(error)
+            }
+            return Ok(table);
+        }
+    }
+
+    fn give_back(&self, table: *mut Table) {
+        /* A pager that stopped writing after a torn write is not reused */
+        unsafe {
+            let mut idle = self.idle.lock().unwrap();
+            if !(*(*table).pager).crashed &&
+                   idle.tables.len() < DATABASE_MAX_IDLE_PAGERS {
+                idle.tables.push(table);
+                return
+            }
+            drop(idle);
+            db_close(table).ok();
+        }
+    }
+
+    fn close_idle(&self) {
+        /*
+  Idle pagers hold the CONNECTION byte like any open connection,
+  so .vacuum closes them first or it would never find itself the
+  only one.
+  */
+        let tables: Vec<*mut Table> =
+            ::std::mem::take(&mut self.idle.lock().unwrap().tables);
+        for table in tables { unsafe { db_close(table).ok(); } }
+    }
+
+    pub fn set_journal_mode(&self, journal_mode: JournalMode)
+     -> Result<(), Error> {
+        /*
+  Used by the pagers handed out from now on, connections that
+  already ran a statement keep the mode they have.
+  */
+        let mut idle = self.idle.lock().unwrap();
+        idle.journal_mode = journal_mode;
+        let mut result: Result<(), Error> = Ok(());
+        for table in idle.tables.iter() {
+            unsafe {
+                let mut pager: *mut Pager = (**table).pager;
+                result =
+                    result.and(pager_statement_finish(pager,
+                                                      pager_set_journal_mode(pager,
+                                                                             journal_mode)));
+            }
+        }
+        return result;
+    }
+
+    pub fn set_synchronous(&self, synchronous: Synchronous) {
+        /* Like set_journal_mode, for the pagers handed out from now on */
+        let mut idle = self.idle.lock().unwrap();
+        idle.synchronous = synchronous;
+        for table in idle.tables.iter() {
+            unsafe { (*(**table).pager).synchronous = synchronous; }
+        }
+    }
+
+    pub fn insert(&self, table: &str, row: Row) -> Result<(), Error> {
+        let name =
+            ::std::ffi::CString::new(table).map_err(|_| Error::NulByte)?;
+        let catalog: *mut Table = self.take()?;
+        let mut statement: Statement = new_statement();
+        statement.row_to_insert = row;
+        unsafe {
+            let mut pager: *mut Pager = (*catalog).pager;
+            pager_release(pager);
+            let result: Result<ExecuteResult, Error> =
+                pager_begin_read(pager).and_then(|_|
+                                                     table_insert(catalog,
+                                                                  name.as_ptr(),
+                                                                  &mut statement));
+            let result: Result<ExecuteResult, Error> =
+                pager_statement_finish(pager, result);
+            self.give_back(catalog);
+            result?;
+            return Ok(());
+        }
+    }
+
+    pub fn get(&self, table: &str, id: u32)
+     -> Result<Option<Row>, Error> {
+        let name =
+            ::std::ffi::CString::new(table).map_err(|_| Error::NulByte)?;
+        let catalog: *mut Table = self.take()?;
+        unsafe {
+            let mut pager: *mut Pager = (*catalog).pager;
+            pager_release(pager);
+            let result: Result<Option<Row>, Error> =
+                pager_begin_read(pager).and_then(|_|
+                                                     table_get(catalog,
+                                                               name.as_ptr(),
+                                                               id));
+            let result: Result<Option<Row>, Error> =
+                pager_statement_finish(pager, result);
+            self.give_back(catalog);
+            return result;
+        }
+    }
+
+    pub fn scan(&self, table: &str) -> Result<Scan<'_>, Error> {
+        /*
+  The scan reads one snapshot of the table, the last one committed
+  when it starts. It does not hold the database, so the thread
+  iterating may call into it meanwhile.
+  */
+        let name =
+            ::std::ffi::CString::new(table).map_err(|_| Error::NulByte)?;
+        let catalog: *mut Table = self.take()?;
+        unsafe {
+            let mut pager: *mut Pager = (*catalog).pager;
+            pager_release(pager);
+            let result: Result<*mut Cursor, Error> =
+                pager_begin_read(pager).and_then(|_|
+                                                     table_scan_start(catalog,
+                                                                      name.as_ptr()));
+            match result {
+                Ok(cursor) => {
+                    return Ok(Scan{database: self,
+                                   table: catalog,
+                                   cursor: cursor,
+                                   result: Ok(()),})
+                }
+                Err(error) => {
+                    pager_statement_finish(pager, Err::<(), Error>(error)).ok();
+                    self.give_back(catalog);
+                    return Err(error)
+                }
+            }
+        }
+    }
+
+    pub fn close(mut self) -> Result<(), Error> {
+        /* Same as dropping it, but says whether the last sync went through */
+        let tables: Vec<*mut Table> =
+            ::std::mem::take(&mut self.idle.get_mut().unwrap().tables);
+        let mut result: Result<(), Error> = Ok(());
+        for table in tables { result = result.and(unsafe { db_close(table) }); }
+        return result;
+    }
+}
+
+pub(crate) unsafe fn table_open(mut catalog: *mut Table,
+                         mut name: *const libc::c_char,
+                         mut statement: *mut Statement, mut writes: bool)
+ -> Result<*mut Table, Error> {
+    /* Look the table up the way the parser would */
+    catalog_load(catalog)?;
+    match prepare_table(statement, catalog, name, writes) {
+        PrepareResult::Success => { }
+        error => { return Err(Error::Syntax(error)) }
+    }
+    return Ok(catalog_find(catalog, name));
+}
+
+pub(crate) unsafe fn table_insert(mut catalog: *mut Table,
+                           mut name: *const libc::c_char,
+                           mut statement: *mut Statement)
+ -> Result<ExecuteResult, Error> {
+    (*statement).type_0 = STATEMENT_INSERT;
+    let mut table: *mut Table =
+        table_open(catalog, name, statement, 1 as libc::c_int != 0)?;
+    /* The row did not come through prepare_insert, check it here */
+    match row_check((*table).schema, &mut (*statement).row_to_insert) {
+        PrepareResult::Success => {
+            return execute_statement(statement, catalog)
+        }
+        error => { return Err(Error::Syntax(error)) }
+    };
+}
+
+pub(crate) unsafe fn table_get(mut catalog: *mut Table,
+                        mut name: *const libc::c_char, mut id: uint32_t)
+ -> Result<Option<Row>, Error> {
+    let mut statement: Statement = new_statement();
+    let mut table: *mut Table =
+        table_open(catalog, name, &mut statement, 0 as libc::c_int != 0)?;
+    let mut cursor: *mut Cursor = table_find(table, id)?;
+    let mut result: Result<Option<Row>, Error> = cursor_get(cursor, id);
+    free(cursor as *mut libc::c_void);
+    return result;
+}
+
+pub(crate) unsafe fn cursor_get(mut cursor: *mut Cursor, mut id: uint32_t)
+ -> Result<Option<Row>, Error> {
+    /* The row under the cursor, if it is the one with this id */
+    let mut node: *mut libc::c_void =
+        get_page((*(*cursor).table).pager, (*cursor).page_num)?;
+    if (*cursor).cell_num >= *leaf_node_num_cells(node) ||
+           *leaf_node_key(node, (*cursor).cell_num) != id {
+        return Ok(None)
+    }
+    let mut row: Row = Row{values: Vec::new(),};
+    cursor_row(cursor, &mut row)?;
+    return Ok(Some(row));
+}
+
+pub(crate) unsafe fn table_scan_start(mut catalog: *mut Table,
+                               mut name: *const libc::c_char)
+ -> Result<*mut Cursor, Error> {
+    let mut statement: Statement = new_statement();
+    let mut table: *mut Table =
+        table_open(catalog, name, &mut statement, 0 as libc::c_int != 0)?;
+    return table_start(table);
+}
+
+impl<'a> Iterator for Scan<'a> {
+    type Item = Result<Row, Error>;
+
+    fn next(&mut self) -> Option<Result<Row, Error>> {
+        unsafe {
+            if self.result.is_err() || (*self.cursor).end_of_table {
+                return None
+            }
+            pager_release((*self.table).pager);
+            let mut row: Row = Row{values: Vec::new(),};
+            /* The scan ends at the first error */
+            self.result = cursor_row(self.cursor, &mut row);
+            if self.result.is_ok() {
+                self.result = cursor_advance(self.cursor)
+            }
+            if let Err(error) = self.result { return Some(Err(error)) }
+            return Some(Ok(row));
+        }
+    }
+}
+
+impl<'a> Drop for Scan<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            free(self.cursor as *mut libc::c_void);
+            /* Unlocking drops the read mark */
+            pager_statement_finish((*self.table).pager, self.result).ok();
+        }
+        self.database.give_back(self.table);
+    }
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        /* Connections hold the Arc, so every pager is idle by now */
+        for table in self.idle.get_mut().unwrap().tables.drain(..) {
+            unsafe { db_close(table).ok(); }
+        }
+    }
+}
+
+impl Connection {
+    pub fn new(database: &::std::sync::Arc<Database>) -> Connection {
+        return Connection{database: database.clone(),
+                          table: 0 as *mut Table,};
+    }
+
+    fn run<T>(&mut self, text: &str,
+                  f: impl FnOnce(*mut InputBuffer, *mut Table)
+                      -> Result<T, Error>) -> Result<T, Error> {
+        /*
+  Hand a line of input to f on this connection's pager, then
+  commit unless a transaction is open.
+  */
+        let text =
+            ::std::ffi::CString::new(text).map_err(|_| Error::NulByte)?;
+        if self.table.is_null() { self.table = self.database.take()?; }
+        let length = text.as_bytes().len();
+        /* The parsers tokenize the buffer in place, so it must be owned */
+        let mut text: Vec<u8> = text.into_bytes_with_nul();
+        unsafe {
+            let mut pager: *mut Pager = (*self.table).pager;
+            let mut input_buffer: InputBuffer =
+                InputBuffer{buffer: text.as_mut_ptr() as *mut char,
+                            buffer_length: text.len(),
+                            input_length: length as ssize_t,};
+            pager_release(pager);
+            let mut result: Result<T, Error> =
+                f(&mut input_buffer, self.table);
+            /* .vacuum may have replaced the pager */
+            pager = (*self.table).pager;
+            return pager_statement_finish(pager, result);
+        }
+    }
+
+    pub fn execute(&mut self, sql: &str) -> Result<Executed, Error> {
+        /* Run one statement, as if it was typed into the REPL */
+        return self.run(sql,
+                        |input_buffer, table| unsafe {
+                            let mut statement: Statement = new_statement();
+                            /* Parsing needs the columns of the tables */
+                            pager_begin_read((*table).pager)?;
+                            catalog_load(table)?;
+                            match prepare_statement(input_buffer,
+                                                    &mut statement, table) {
+                                PrepareResult::Success => { }
+                                error => { return Err(Error::Syntax(error)) }
+                            }
+                            let mut result: ExecuteResult =
+                                execute_statement(&mut statement, table)?;
+                            Ok(Executed{result: result,
+                                        rows: statement.rows,
+                                        output: statement.output,})
+                        });
+    }
+
+    pub fn meta_command(&mut self, command: &str) -> Result<String, Error> {
+        /* Returns what the REPL prints for the command */
+        if command == ".vacuum" { self.database.close_idle(); }
+        return self.run(command,
+                        |input_buffer, table| unsafe {
+                            let mut output: String = String::new();
+                            match do_meta_command(input_buffer, table,
+                                                  &mut output)? {
+                                META_COMMAND_SUCCESS => { Ok(output) }
+                                META_COMMAND_UNRECOGNIZED_COMMAND => {
+                                    Err(Error::UnrecognizedCommand)
+                                }
+                            }
+                        });
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        /* A transaction that was never committed does not survive */
+        if self.table.is_null() { return }
+        unsafe {
+            let mut pager: *mut Pager = (*self.table).pager;
+            if (*pager).in_transaction {
+                pager_statement_finish(pager, pager_rollback(pager)).ok();
+            }
+        }
+        self.database.give_back(self.table);
+    }
+}
+
+impl ::std::fmt::Display for Error {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        /* The same messages the REPL used to print before exiting */
+        let (format_version, min_page_size, max_page_size) =
+            unsafe { (FILE_FORMAT_VERSION, MIN_PAGE_SIZE, MAX_PAGE_SIZE) };
+        match *self {
+            Error::Io(action, errno) => {
+                write!(f, "Error {}: {}", action, errno)
+            }
+            Error::Full => { write!(f, "Error: database or disk is full.") }
+            Error::Corrupt(page_num) => {
+                write!(f, "Error: Database page {} is corrupt.", page_num)
+            }
+            Error::NotADatabase => {
+                write!(f, "File is not a bizdb database.")
+            }
+            Error::Version(version) if version > format_version => {
+                write!(f,
+                       "Database format version {} is newer than supported version {}.",
+                       version, format_version)
+            }
+            Error::Version(0) => {
+                write!(f,
+                       "Database file predates the bizdb file header and cannot be upgraded.")
+            }
+            Error::Version(version) => {
+                write!(f,
+                       "Database format version {} is older than supported version {} and cannot be upgraded.",
+                       version, format_version)
+            }
+            Error::PageSize(page_size) => {
+                write!(f,
+                       "Page size {} is not a power of two from {} to {}.",
+                       page_size, min_page_size, max_page_size)
+            }
+            Error::DuplicateKey => { write!(f, "Error: Duplicate key.") }
+            Error::TableExists => {
+                write!(f, "Error: Table already exists.")
+            }
+            Error::NulByte => {
+                write!(f, "Error: Input contains a NUL byte.")
+            }
+            Error::TransactionActive => {
+                write!(f, "Error: Not allowed inside a transaction.")
+            }
+            Error::UnrecognizedCommand => {
+                write!(f, "Unrecognized command.")
+            }
+            Error::Syntax(PrepareResult::NegativeId) => {
+                write!(f, "ID must be positive.")
+            }
+            Error::Syntax(PrepareResult::StringTooLong) => {
+                write!(f, "String is too long.")
+            }
+            Error::Syntax(PrepareResult::UnrecognizedStatement) => {
+                write!(f, "Unrecognized keyword at start of statement.")
+            }
+            Error::Syntax(PrepareResult::UnrecognizedPragma) => {
+                write!(f, "Unrecognized pragma.")
+            }
+            Error::Syntax(PrepareResult::NoSuchTable) => {
+                write!(f, "Error: No such table.")
+            }
+            Error::Syntax(PrepareResult::ReadOnly) => {
+                write!(f, "Error: The catalog is read-only.")
+            }
+            Error::Syntax(PrepareResult::TypeMismatch) => {
+                write!(f, "Value does not match the column type.")
+            }
+            Error::Syntax(PrepareResult::NullId) => {
+                write!(f, "ID must not be null.")
+            }
+            Error::Syntax(PrepareResult::IdTooLarge) => {
+                write!(f, "ID is too large.")
+            }
+            Error::Syntax(_) => {
+                write!(f, "Syntax error. Could not parse statement.")
+            }
+            Error::Locked => { write!(f, "Error: database is locked.") }
+        }
+    }
+}
+
+impl ::std::error::Error for Error { }
+
+unsafe extern "C" fn run_static_initializers() {
+    IS_ROOT_OFFSET = NODE_TYPE_SIZE;
+    PARENT_POINTER_OFFSET =
+        IS_ROOT_OFFSET.wrapping_add(IS_ROOT_SIZE).wrapping_add(NODE_HEADER_PADDING_SIZE);
+    COMMON_NODE_HEADER_SIZE =
+        PARENT_POINTER_OFFSET.wrapping_add(PARENT_POINTER_SIZE) as uint8_t;
+    INTERNAL_NODE_NUM_KEYS_OFFSET = COMMON_NODE_HEADER_SIZE as uint32_t;
+    INTERNAL_NODE_RIGHT_CHILD_OFFSET =
+        INTERNAL_NODE_NUM_KEYS_OFFSET.wrapping_add(INTERNAL_NODE_NUM_KEYS_SIZE);
+    INTERNAL_NODE_HEADER_SIZE =
+        (COMMON_NODE_HEADER_SIZE as
+             libc::c_uint).wrapping_add(INTERNAL_NODE_NUM_KEYS_SIZE).wrapping_add(INTERNAL_NODE_RIGHT_CHILD_SIZE);
+    INTERNAL_NODE_CELL_SIZE =
+        INTERNAL_NODE_CHILD_SIZE.wrapping_add(INTERNAL_NODE_KEY_SIZE);
+    LEAF_NODE_NUM_CELLS_OFFSET = COMMON_NODE_HEADER_SIZE as uint32_t;
+    LEAF_NODE_NEXT_LEAF_OFFSET =
+        LEAF_NODE_NUM_CELLS_OFFSET.wrapping_add(LEAF_NODE_NUM_CELLS_SIZE);
+    LEAF_NODE_CONTENT_OFFSET_OFFSET =
+        LEAF_NODE_NEXT_LEAF_OFFSET.wrapping_add(LEAF_NODE_NEXT_LEAF_SIZE);
+    LEAF_NODE_HEADER_SIZE =
+        (COMMON_NODE_HEADER_SIZE as
+             libc::c_uint).wrapping_add(LEAF_NODE_NUM_CELLS_SIZE).wrapping_add(LEAF_NODE_NEXT_LEAF_SIZE).wrapping_add(LEAF_NODE_CONTENT_OFFSET_SIZE);
+    LEAF_NODE_PAYLOAD_SIZE_OFFSET =
+        LEAF_NODE_KEY_OFFSET.wrapping_add(LEAF_NODE_KEY_SIZE);
+    LEAF_NODE_CELL_HEADER_SIZE =
+        LEAF_NODE_PAYLOAD_SIZE_OFFSET.wrapping_add(LEAF_NODE_PAYLOAD_SIZE_SIZE);
+    let mut i: uint32_t = 0 as libc::c_int as uint32_t;
+    while i < 256 as libc::c_int as libc::c_uint {
+        let mut crc: uint32_t = i;
+        let mut bit: libc::c_int = 0 as libc::c_int;
+        while bit < 8 as libc::c_int {
+            if crc & 1 as libc::c_int as libc::c_uint != 0 {
+                crc = 0xedb88320 as libc::c_uint ^ crc >> 1 as libc::c_int
+            } else { crc >>= 1 as libc::c_int }
+            bit += 1
+        }
+        CRC32_TABLE[i as usize] = crc;
+        i = i.wrapping_add(1)
+    }
+    OVERFLOW_PAGE_HEADER_SIZE =
+        OVERFLOW_PAGE_NEXT_OFFSET.wrapping_add(OVERFLOW_PAGE_NEXT_SIZE);
+    FILE_HEADER_VERSION_OFFSET =
+        FILE_HEADER_MAGIC_OFFSET.wrapping_add(FILE_HEADER_MAGIC_SIZE);
+    FILE_HEADER_PAGE_SIZE_OFFSET =
+        FILE_HEADER_VERSION_OFFSET.wrapping_add(FILE_HEADER_VERSION_SIZE);
+    FILE_HEADER_ROOT_PAGE_OFFSET =
+        FILE_HEADER_PAGE_SIZE_OFFSET.wrapping_add(FILE_HEADER_PAGE_SIZE_SIZE);
+    FILE_HEADER_FREELIST_TRUNK_OFFSET =
+        FILE_HEADER_ROOT_PAGE_OFFSET.wrapping_add(FILE_HEADER_ROOT_PAGE_SIZE);
+    FILE_HEADER_FREELIST_COUNT_OFFSET =
+        FILE_HEADER_FREELIST_TRUNK_OFFSET.wrapping_add(FILE_HEADER_FREELIST_TRUNK_SIZE);
+    FILE_HEADER_CHANGE_COUNTER_OFFSET =
+        FILE_HEADER_FREELIST_COUNT_OFFSET.wrapping_add(FILE_HEADER_FREELIST_COUNT_SIZE);
+    FILE_HEADER_SIZE =
+        FILE_HEADER_CHANGE_COUNTER_OFFSET.wrapping_add(FILE_HEADER_CHANGE_COUNTER_SIZE);
+    FREELIST_TRUNK_NUM_LEAVES_OFFSET =
+        FREELIST_TRUNK_NEXT_OFFSET.wrapping_add(FREELIST_TRUNK_NEXT_SIZE);
+    FREELIST_TRUNK_HEADER_SIZE =
+        FREELIST_TRUNK_NEXT_SIZE.wrapping_add(FREELIST_TRUNK_NUM_LEAVES_SIZE);
+}
+#[used]
+#[cfg_attr(target_os = "linux", link_section = ".init_array")]
+#[cfg_attr(target_os = "windows", link_section = ".CRT$XIB")]
+#[cfg_attr(target_os = "macos", link_section = "__DATA,__mod_init_func")]
+static INIT_ARRAY: [unsafe extern "C" fn(); 1] = [run_static_initializers];
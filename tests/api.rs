@@ -0,0 +1,178 @@
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+
+fn user(id: u32, username: &str, email: &str) -> Row {
+    Row::new(vec![Value::Integer(id.into()), Value::Text(username.to_string()),
+                  Value::Text(email.to_string())])
+}
+
+#[test]
+fn rows_round_trip() {
+    let db = TempDb::new("api");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    assert_eq!(database.insert("users", user(1, "u", "e")),
+               Err(Error::Syntax(PrepareResult::NoSuchTable)));
+    Connection::new(&database).execute(CREATE_USERS).unwrap();
+    /* Rows are checked against the columns of the table */
+    assert_eq!(database.insert("users", user(1, &"u".repeat(33), "e")),
+               Err(Error::Syntax(PrepareResult::StringTooLong)));
+    assert!(database.insert("users", user(1, "u", &"e".repeat(256))).is_err());
+    assert!(database.insert("users", Row::new(vec![Value::Integer(1)]))
+                .is_err());
+    for id in &[3, 1, 2] {
+        database.insert("users", user(*id, &format!("user{}", id), "e"))
+            .unwrap();
+    }
+    assert_eq!(database.insert("users", user(2, "again", "e")),
+               Err(Error::DuplicateKey));
+    assert_eq!(database.get("users", 2).unwrap(), Some(user(2, "user2", "e")));
+    assert!(database.get("users", 4).unwrap().is_none());
+    let ids: Vec<u32> =
+        database.scan("users").unwrap()
+            .map(|row| row.unwrap().id().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    /* Rows built by hand need not start with a key */
+    assert_eq!(Row::new(vec![Value::Text("1".to_string())]).id(), None);
+    assert_eq!(Row::new(vec![Value::Integer(1 << 32)]).id(), None);
+    assert_eq!(Row::new(vec![Value::Integer(-1)]).id(), None);
+    Arc::try_unwrap(database).ok().unwrap().close().unwrap();
+    assert_eq!(select_ids(&db.path), vec![1, 2, 3]);
+}
+
+#[test]
+fn errors_are_returned() {
+    let db = TempDb::new("errors");
+    std::fs::write(&db.path, b"not a database").unwrap();
+    assert_eq!(Database::open(&db.path).err(), Some(Error::NotADatabase));
+    std::fs::remove_file(&db.path).unwrap();
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    assert_eq!(connection.execute(CREATE_USERS), Err(Error::TableExists));
+    connection.execute("insert into users 1 a a@example.com").unwrap();
+    let error =
+        connection.execute("insert into users 1 b b@example.com")
+            .unwrap_err();
+    assert_eq!(error, Error::DuplicateKey);
+    assert_eq!(error.to_string(), "Error: Duplicate key.");
+    assert_eq!(connection.execute("insert into users -1 b b"),
+               Err(Error::Syntax(PrepareResult::NegativeId)));
+    assert_eq!(connection.execute("drop everything"),
+               Err(Error::Syntax(PrepareResult::UnrecognizedStatement)));
+    /* A failed statement leaves the connection usable */
+    connection.execute("insert into users 2 b b@example.com").unwrap();
+    assert_eq!(database.scan("users").unwrap().count(), 2);
+    connection.execute("begin").unwrap();
+    assert_eq!(connection.meta_command(".vacuum"),
+               Err(Error::TransactionActive));
+    assert_eq!(connection.meta_command(".checkpoint"),
+               Err(Error::TransactionActive));
+    assert_eq!(Error::TransactionActive.to_string(),
+               "Error: Not allowed inside a transaction.");
+    connection.execute("rollback").unwrap();
+}
+
+#[test]
+fn nul_bytes_are_errors() {
+    let db = TempDb::new("nul");
+    assert_eq!(Database::open("nul\0.db").err(), Some(Error::NulByte));
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    assert_eq!(connection.execute("insert into users 1 a\0b a@example.com"),
+               Err(Error::NulByte));
+    assert_eq!(connection.meta_command(".check\0"), Err(Error::NulByte));
+    assert_eq!(database.insert("users\0", user(1, "a", "a@example.com")),
+               Err(Error::NulByte));
+    assert_eq!(database.get("users\0", 1).err(), Some(Error::NulByte));
+    assert!(database.scan("users\0").is_err());
+    /* Nothing was left locked */
+    assert_eq!(connection.execute("insert into users 1 a a@example.com")
+                   .map(|executed| executed.result),
+               Ok(ExecuteResult::Success));
+    let error: Box<dyn std::error::Error> = Box::new(Error::NulByte);
+    assert_eq!(error.to_string(), "Error: Input contains a NUL byte.");
+}
+
+#[test]
+fn scans_read_their_snapshot_while_others_write() {
+    let db = TempDb::new("scan-snapshot");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    for id in 1..=50 {
+        connection.execute(&insert_user(id)).unwrap();
+    }
+    let mut scan = database.scan("users").unwrap();
+    assert_eq!(scan.next().unwrap().unwrap().id(), Some(1));
+    /* The thread iterating writes through the database meanwhile */
+    database.insert("users", user(51, "d", "d@example.com")).unwrap();
+    connection.execute("delete from users where id between 1 and 50").unwrap();
+    assert_eq!(database.get("users", 2).unwrap(), None);
+    let rest: Vec<u32> =
+        scan.map(|row| row.unwrap().id().unwrap()).collect();
+    assert_eq!(rest, (2..=50).collect::<Vec<u32>>());
+    let ids: Vec<u32> =
+        database.scan("users").unwrap()
+            .map(|row| row.unwrap().id().unwrap()).collect();
+    assert_eq!(ids, vec![51]);
+}
+
+#[test]
+fn settings_parse_from_their_names() {
+    assert_eq!("rollback".parse(), Ok(JournalMode::Rollback));
+    assert_eq!("normal".parse(), Ok(Synchronous::Normal));
+    assert_eq!("delete".parse::<JournalMode>(),
+               Err(UnknownSetting("delete".to_string())));
+    assert_eq!("FULL".parse::<Synchronous>(),
+               Err(UnknownSetting("FULL".to_string())));
+    assert_eq!(UnknownSetting("FULL".to_string()).to_string(),
+               "Unknown setting 'FULL'.");
+}
+
+#[test]
+fn output_is_returned_rather_than_printed() {
+    let db = TempDb::new("output");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    connection.execute(&insert_user(2)).unwrap();
+    connection.execute(&insert_user(1)).unwrap();
+    let executed = connection.execute("select * from users").unwrap();
+    assert_eq!(executed.result, ExecuteResult::Success);
+    assert_eq!(executed.rows.iter().map(Row::id).collect::<Vec<_>>(),
+               vec![Some(1), Some(2)]);
+    assert_eq!(executed.output, "");
+    let executed = connection.execute("pragma synchronous = normal").unwrap();
+    assert!(executed.rows.is_empty());
+    assert_eq!(executed.output, "synchronous = normal\n");
+    assert_eq!(connection.meta_command(".check"), Ok("ok\n".to_string()));
+    assert_eq!(connection.meta_command(".tables"),
+               Ok("users\n".to_string()));
+    assert_eq!(connection.meta_command(".nonsense"),
+               Err(Error::UnrecognizedCommand));
+}
+
+#[test]
+fn idle_pagers_do_not_count_as_other_connections() {
+    let db = TempDb::new("idle");
+    let wal = format!("{}-wal", db.path);
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    database.insert("users", user(1, "u", "e")).unwrap();
+    assert!(database.get("users", 1).unwrap().is_some());
+    assert_eq!(database.scan("users").unwrap().count(), 1);
+    /* A second connection that has come and gone leaves a pager idle */
+    Connection::new(&database).execute("select * from users").unwrap();
+    assert_eq!(connection.meta_command(".vacuum"), Ok(String::new()));
+    drop(connection);
+    assert!(database.get("users", 1).unwrap().is_some());
+    /* The last pager to close checkpoints and takes the WAL away */
+    Arc::try_unwrap(database).ok().unwrap().close().unwrap();
+    assert!(!std::path::Path::new(&wal).exists());
+    assert_eq!(select_ids(&db.path), vec![1]);
+}
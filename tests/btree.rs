@@ -0,0 +1,136 @@
+mod common;
+
+use common::*;
+
+/* 512 byte pages hold a row or two each, so the tree gets deep quickly */
+fn create_small_pages(path: &str) {
+    repl(&["--page-size=512"], path, &format!("{}\n.exit\n", CREATE_USERS));
+}
+
+fn insert_rows(path: &str, ids: impl Iterator<Item = u32>) -> String {
+    let mut input = String::new();
+    for id in ids {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    input.push_str(".exit\n");
+    repl(&[], path, &input)
+}
+
+fn run(path: &str, statements: impl Iterator<Item = String>) -> String {
+    let mut input: String = statements.map(|line| line + "\n").collect();
+    input.push_str(".exit\n");
+    repl(&[], path, &input)
+}
+
+#[test]
+fn splits() {
+    let db = TempDb::new("splits");
+    create_small_pages(&db.path);
+    /* Interleaved, so splits happen in the middle of nodes as well */
+    let output = insert_rows(&db.path, (1..=400).filter(|id| id % 2 == 0));
+    assert!(!output.contains("Error"), "{}", output);
+    let output =
+        insert_rows(&db.path, (1..=400).rev().filter(|id| id % 2 == 1));
+    assert!(!output.contains("Error"), "{}", output);
+    assert_eq!(select_ids(&db.path), (1..=400).collect::<Vec<u32>>());
+    /* A third level only comes from splitting the internal root */
+    assert!(levels(&db.path) >= 3);
+}
+
+#[test]
+fn internal_nodes_fill_the_page() {
+    let db = TempDb::new("wide-internal");
+    create_users(&db.path);
+    insert_rows(&db.path, 1..=400);
+    /* Some thirty leaves are children of one 4096 byte root */
+    assert_eq!(levels(&db.path), 2);
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn merges() {
+    let db = TempDb::new("merges");
+    create_small_pages(&db.path);
+    insert_rows(&db.path, 1..=200);
+    assert!(levels(&db.path) >= 3);
+    let output =
+        run(&db.path, (1..=200).filter(|id| id % 3 != 0)
+                          .map(|id| format!("delete from users where id = {}",
+                                            id)));
+    assert!(!output.contains("Error"), "{}", output);
+    assert_eq!(select_ids(&db.path),
+               (1..=200).filter(|id| id % 3 == 0).collect::<Vec<u32>>());
+    run(&db.path,
+        std::iter::once("delete from users where id between 1 and 197".into()));
+    assert_eq!(select_ids(&db.path), vec![198]);
+    /* The root collapsed back into a single leaf */
+    assert_eq!(levels(&db.path), 1);
+}
+
+#[test]
+fn freed_pages_are_reused() {
+    let db = TempDb::new("freelist");
+    create_users(&db.path);
+    insert_rows(&db.path, 1..=150);
+    run(&db.path,
+        std::iter::once("delete from users where id between 1 and 150".into()));
+    let length = std::fs::metadata(&db.path).unwrap().len();
+    let freelist = repl(&[], &db.path, ".freelist\n.exit\n");
+    assert!(!freelist.contains("Free pages: 0\n"), "{}", freelist);
+    insert_rows(&db.path, 1..=150);
+    /* Every page came off the free list, none were added */
+    assert_eq!(std::fs::metadata(&db.path).unwrap().len(), length);
+    let freelist = repl(&[], &db.path, ".freelist\n.exit\n");
+    assert!(freelist.contains("Free pages: 0\n"), "{}", freelist);
+    assert_eq!(select_ids(&db.path), (1..=150).collect::<Vec<u32>>());
+}
+
+#[test]
+fn vacuum_compacts_the_file() {
+    let db = TempDb::new("vacuum");
+    create_users(&db.path);
+    insert_rows(&db.path, 1..=150);
+    run(&db.path,
+        std::iter::once("delete from users where id between 1 and 140".into()));
+    let length = std::fs::metadata(&db.path).unwrap().len();
+    let output = repl(&[], &db.path, ".vacuum\n.freelist\n.exit\n");
+    assert!(output.contains("Free pages: 0\n"), "{}", output);
+    assert!(std::fs::metadata(&db.path).unwrap().len() < length);
+    assert_eq!(select_ids(&db.path), (141..=150).collect::<Vec<u32>>());
+}
+
+#[test]
+fn vacuum_keeps_tables_of_any_shape() {
+    let db = TempDb::new("vacuum-tables");
+    create_users(&db.path);
+    insert_rows(&db.path, 1..=50);
+    /* One column, and more columns than users has */
+    let mut statements: Vec<String> =
+        vec!["create table keys (id integer)".into(),
+             "create table wide (id integer, a text, b integer, c real, \
+              d boolean)".into()];
+    for id in 1..=100 {
+        statements.push(format!("insert into keys {}", id));
+        statements.push(format!("insert into wide {} text{} {} 1.5 true",
+                                id, id, id * 2));
+    }
+    statements.push("delete from keys where id between 1 and 90".into());
+    statements.push("delete from wide where id between 11 and 100".into());
+    run(&db.path, statements.into_iter());
+    let output = repl(&[], &db.path, ".vacuum\n.exit\n");
+    assert!(!output.contains("Error"), "{}", output);
+    assert_eq!(check(&db.path), "ok");
+    assert_eq!(select_ids(&db.path), (1..=50).collect::<Vec<u32>>());
+    let output = run(&db.path,
+                     vec!["select * from keys".to_string(),
+                          "select * from wide".to_string()]
+                         .into_iter());
+    let rows: Vec<&str> =
+        output.lines().map(|line| line.trim_start_matches("db > "))
+            .filter(|line| line.starts_with('(')).collect();
+    let mut expected: Vec<String> =
+        (91..=100).map(|id| format!("({})", id)).collect();
+    expected.extend((1..=10).map(|id| format!("({}, text{}, {}, 1.5, true)",
+                                              id, id, id * 2)));
+    assert_eq!(rows, expected);
+}
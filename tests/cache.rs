@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn small_cache_keeps_every_row() {
+    /* Far more pages than the cache holds, so most get evicted */
+    let db = TempDb::new("small-cache");
+    create_users(&db.path);
+    let mut input = String::from(".cache_size 10\n");
+    for id in (1..=1000).rev() {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    input.push_str(".cache_size\n.exit\n");
+    let output = repl(&[], &db.path, &input);
+    assert!(output.contains("Cache size: 10 pages\n"), "{}", output);
+    assert!(!output.contains("Error"), "{}", output);
+    assert_eq!(select_ids(&db.path), (1..=1000).collect::<Vec<u32>>());
+}
+
+#[test]
+fn only_modified_pages_are_written() {
+    let db = TempDb::new("dirty");
+    create_users(&db.path);
+    let mut input = String::new();
+    for id in 1..=100 {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    input.push_str(".exit\n");
+    repl(&[], &db.path, &input);
+    /* A 2 page cache evicts while select reads the whole table */
+    let output = repl(&[], &db.path,
+                      ".cache_size 2\nselect * from users\n.stats\n\
+                       update users set username = changed where id = 50\n\
+                       select * from users\n.stats\n.exit\n");
+    let written: Vec<&str> =
+        output.lines().filter(|line| line.contains("Pages written")).collect();
+    /* The leaf holding the row, and the header with its change counter */
+    assert_eq!(written, vec!["Pages written: 0", "Pages written: 2"],
+               "{}", output);
+}
+
+#[test]
+fn cache_shrinks_back_after_a_split() {
+    /*
+  Splitting an internal node holds every child at once, more
+  pages than the cache allows. Later misses evict down again.
+  */
+    let db = TempDb::new("cache");
+    let mut input = format!("{}\n.cache_size 10\n", CREATE_USERS);
+    for id in 1..=1000 {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    input.push_str("select * from users where id > 1000\n.cache_size\n.exit\n");
+    let output = repl(&["--page-size=512"], &db.path, &input);
+    assert!(output.contains("Cache size: 10 pages\nCached pages: 10\n"),
+            "{}", &output[output.len() - 100..]);
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn savepoint_snapshots_are_kept_out_of_memory() {
+    let db = TempDb::new("savepoint-file");
+    create_users(&db.path);
+    let mut input = String::from(".cache_size 2\nbegin\n");
+    for id in 1..=300 {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    /* The delete has the savepoint copy most of the leaves */
+    input.push_str("savepoint a\n\
+                    delete from users where id between 1 and 250\n\
+                    rollback to a\nrelease a\ncommit\n.exit\n");
+    let output = repl(&[], &db.path, &input);
+    assert!(!output.contains("Error"), "{}", output);
+    assert_eq!(select_ids(&db.path), (1..=300).collect::<Vec<u32>>());
+    /* The file the copies went to is gone without a trace */
+    let name = std::path::Path::new(&db.path).file_name().unwrap()
+        .to_str().unwrap().to_string();
+    let leftovers: Vec<_> =
+        std::fs::read_dir(std::env::temp_dir()).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|file| file.to_str().unwrap()
+                        .starts_with(&format!("{}-savepoint", name)))
+            .collect();
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::*;
+
+const PAGE_SIZE: usize = 4096;
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb88320 ^ crc >> 1 } else { crc >> 1 };
+        }
+    }
+    crc ^ 0xffffffff
+}
+
+fn field(file: &[u8], page: usize, offset: usize) -> u32 {
+    let start = page * PAGE_SIZE + offset;
+    u32::from_le_bytes([file[start], file[start + 1], file[start + 2],
+                        file[start + 3]])
+}
+
+fn set_field(file: &mut [u8], page: usize, offset: usize, value: u32) {
+    let start = page * PAGE_SIZE + offset;
+    file[start..start + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/* Recompute the checksum, so only the tree walk can find the damage */
+fn seal(file: &mut [u8], page: usize) {
+    let start = page * PAGE_SIZE;
+    let crc = crc32(&file[start..start + PAGE_SIZE - 4]);
+    set_field(file, page, PAGE_SIZE - 4, crc);
+}
+
+/* Leaves other than the last one, in file order */
+fn leaves(path: &str) -> (Vec<u8>, Vec<usize>) {
+    let mut input = String::new();
+    for id in 1..=200 {
+        input.push_str(&format!("{}\n", insert_user(id)));
+    }
+    input.push_str(".exit\n");
+    repl(&[], path, &input);
+    assert_eq!(check(path), "ok");
+    let file = std::fs::read(path).unwrap();
+    /* Node type 1 is a leaf, with the number of cells and next leaf at 8 */
+    let leaves =
+        (1..file.len() / PAGE_SIZE).filter(|page| {
+                                            file[page * PAGE_SIZE] == 1 &&
+                                                field(&file, *page, 8) > 1 &&
+                                                field(&file, *page, 12) != 0
+                                        }).collect();
+    (file, leaves)
+}
+
+#[test]
+fn checksum_mismatch_is_reported() {
+    let db = TempDb::new("check-checksum");
+    create_users(&db.path);
+    let (mut file, leaves) = leaves(&db.path);
+    file[leaves[0] * PAGE_SIZE + 100] ^= 0xff;
+    std::fs::write(&db.path, &file).unwrap();
+    assert_eq!(check(&db.path),
+               format!("Page {}: checksum mismatch\n1 problem found.",
+                       leaves[0]));
+}
+
+#[test]
+fn every_tree_problem_is_reported() {
+    let db = TempDb::new("check-tree");
+    create_users(&db.path);
+    let (mut file, leaves) = leaves(&db.path);
+    /* Cut the leaf chain after the first leaf */
+    let next = field(&file, leaves[0], 12);
+    set_field(&mut file, leaves[0], 12, 0);
+    seal(&mut file, leaves[0]);
+    /* Flag a leaf further on as the root */
+    file[leaves[2] * PAGE_SIZE + 1] = 1;
+    seal(&mut file, leaves[2]);
+    std::fs::write(&db.path, &file).unwrap();
+    let report = check(&db.path);
+    assert!(report.contains(&format!("Page {}: next leaf is 0, expected {}",
+                                     leaves[0], next)), "{}", report);
+    assert!(report.contains(&format!("Page {}: flagged as root", leaves[2])),
+            "{}", report);
+    assert!(report.ends_with("2 problems found."), "{}", report);
+}
+
+#[test]
+fn misaligned_cell_is_reported() {
+    let db = TempDb::new("check-aligned");
+    create_users(&db.path);
+    let (mut file, leaves) = leaves(&db.path);
+    /* Cell pointers follow the 20 byte leaf header, 2 bytes each */
+    let start = leaves[0] * PAGE_SIZE + 20;
+    let pointer = u16::from_le_bytes([file[start], file[start + 1]]) + 2;
+    file[start..start + 2].copy_from_slice(&pointer.to_le_bytes());
+    seal(&mut file, leaves[0]);
+    std::fs::write(&db.path, &file).unwrap();
+    let report = check(&db.path);
+    assert!(report.starts_with(&format!("Page {}: cell 0 lies outside the cell content",
+                                        leaves[0])), "{}", report);
+    /* Reading the row fails the same way instead of misreading it */
+    let database = bizdb::Database::open(&db.path).unwrap();
+    let error = database.scan("users").unwrap().find_map(|row| row.err());
+    assert_eq!(error, Some(bizdb::Error::Corrupt(leaves[0] as u32)));
+}
+
+#[test]
+fn cell_counts_that_overrun_the_page_are_corrupt() {
+    let db = TempDb::new("check-cells");
+    create_users(&db.path);
+    let (mut file, leaves) = leaves(&db.path);
+    /* More cells than the page has room for pointers to */
+    set_field(&mut file, leaves[0], 8, 100000);
+    seal(&mut file, leaves[0]);
+    std::fs::write(&db.path, &file).unwrap();
+    let report = check(&db.path);
+    assert!(report.starts_with(&format!("Page {}: 100000 cells", leaves[0])),
+            "{}", report);
+    let database = bizdb::Database::open(&db.path).unwrap();
+    let error = database.scan("users").unwrap().find_map(|row| row.err());
+    assert_eq!(error, Some(bizdb::Error::Corrupt(leaves[0] as u32)));
+}
+
+#[test]
+fn key_counts_that_overrun_the_page_are_corrupt() {
+    let db = TempDb::new("check-keys");
+    create_users(&db.path);
+    let (mut file, _) = leaves(&db.path);
+    let root = (1..file.len() / PAGE_SIZE)
+        .find(|page| file[page * PAGE_SIZE] == 0).unwrap();
+    set_field(&mut file, root, 8, 100000);
+    seal(&mut file, root);
+    std::fs::write(&db.path, &file).unwrap();
+    let database = bizdb::Database::open(&db.path).unwrap();
+    assert_eq!(database.get("users", 1).err(),
+               Some(bizdb::Error::Corrupt(root as u32)));
+}
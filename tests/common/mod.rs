@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+/* A database file in the temp directory, removed with its WAL and journal */
+pub struct TempDb {
+    pub path: String,
+}
+
+impl TempDb {
+    pub fn new(name: &str) -> TempDb {
+        let path = std::env::temp_dir().join(format!("bizdb-{}-{}.db", name,
+                                                    std::process::id()));
+        let db = TempDb{path: path.to_str().unwrap().to_string(),};
+        db.remove();
+        db
+    }
+
+    fn remove(&self) {
+        for suffix in &["", "-wal", "-journal", "-vacuum", "-vacuum-wal"] {
+            std::fs::remove_file(format!("{}{}", self.path, suffix)).ok();
+        }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) { self.remove(); }
+}
+
+/* The table the tests store their rows in */
+pub const CREATE_USERS: &str =
+    "create table users (id integer, username text(32), email text(255))";
+
+/* A row wide enough that a 4096 byte leaf takes about a dozen of them */
+pub fn insert_user(id: u32) -> String {
+    format!("insert into users {} user{} person{}@{}.com", id, id, id,
+            "x".repeat(200))
+}
+
+pub fn create_users(path: &str) {
+    assert_eq!(repl(&[], path, &format!("{}\n.exit\n", CREATE_USERS)),
+               "db > Executed.\ndb > ");
+}
+
+/* Feed lines to the REPL and return what it printed */
+pub fn repl(args: &[&str], path: &str, input: &str) -> String {
+    let mut child =
+        Command::new(env!("CARGO_BIN_EXE_bizdb")).args(args).arg(path)
+            .stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+    /* A REPL that refuses the file exits before reading its input */
+    child.stdin.take().unwrap().write_all(input.as_bytes()).ok();
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/* What .check says about a database that is not open elsewhere */
+pub fn check(path: &str) -> String {
+    let output = repl(&[], path, ".check\n.exit\n");
+    output.trim_start_matches("db > ").trim_end_matches("db > ").trim()
+        .to_string()
+}
+
+/* The ids select prints, in the order it prints them */
+pub fn select_ids(path: &str) -> Vec<u32> {
+    repl(&[], path, "select * from users\n.exit\n").lines()
+        .filter_map(|line| line.trim_start_matches("db > ").strip_prefix('('))
+        .map(|row| row.split(',').next().unwrap().parse().unwrap()).collect()
+}
+
+/* Depth of the tree .btree prints, counting the leaves as one level */
+pub fn levels(path: &str) -> usize {
+    let output = repl(&[], path, ".btree\n.exit\n");
+    let tree = output.split("Tree of users:").nth(1).unwrap();
+    /* Leaves are nested one level deeper than their parent */
+    let leaf = tree.lines().find(|line| line.contains("- leaf")).unwrap();
+    (leaf.len() - leaf.trim_start().len()) / 2 + 1
+}
+
+/* A REPL kept running, to hold locks and transactions between lines */
+pub struct Repl {
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+    lines: u32,
+}
+
+impl Repl {
+    pub fn open(args: &[&str], path: &str) -> Repl {
+        let mut child =
+            Command::new(env!("CARGO_BIN_EXE_bizdb")).args(args).arg(path)
+                .stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()
+                .unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Repl{child, stdout, lines: 0,}
+    }
+
+    /* Run one line and return the lines it printed, prompts left out */
+    pub fn run(&mut self, line: &str) -> Vec<String> {
+        /* An unknown command echoes the marker once the line is done */
+        self.lines += 1;
+        let marker = format!(".done{}", self.lines);
+        let stdin = self.child.stdin.as_mut().unwrap();
+        write!(stdin, "{}\n{}\n", line, marker).unwrap();
+        stdin.flush().unwrap();
+        let mut output: Vec<String> = Vec::new();
+        loop {
+            let mut text = String::new();
+            if self.stdout.read_line(&mut text).unwrap() == 0 {
+                panic!("REPL exited during '{}': {:?}", line, output);
+            }
+            if text.contains(&marker) { break }
+            let text = text.replace("db > ", "");
+            if !text.trim().is_empty() { output.push(text.trim().to_string()) }
+        }
+        output
+    }
+
+    /* Quit the way a crash would, without committing or checkpointing */
+    pub fn kill(mut self) {
+        self.child.kill().unwrap();
+        self.child.wait().unwrap();
+    }
+
+    pub fn exit(mut self) {
+        self.child.stdin.as_mut().unwrap().write_all(b".exit\n").unwrap();
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+impl Drop for Repl {
+    fn drop(&mut self) {
+        /* A failed test must not leave the process behind */
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+
+/* What the REPL prints when it is started on the file */
+fn open_error(path: &str) -> String {
+    repl(&[], path, ".exit\n").trim().to_string()
+}
+
+fn legacy_leaf(num_cells: u32) -> Vec<u8> {
+    /* A root leaf the way the first builds wrote it */
+    let mut page = vec![0u8; 4096];
+    page[0] = 1;
+    page[1] = 1;
+    page[8..12].copy_from_slice(&num_cells.to_le_bytes());
+    page
+}
+
+fn assert_refused(db: &TempDb, file: &[u8], error: Error) {
+    std::fs::write(&db.path, file).unwrap();
+    assert_eq!(Database::open(&db.path).err(), Some(error));
+    /* Refused before a byte of it was touched */
+    assert_eq!(std::fs::read(&db.path).unwrap(), file);
+}
+
+#[test]
+fn foreign_files_are_refused() {
+    let db = TempDb::new("foreign");
+    std::fs::write(&db.path, b"not a database").unwrap();
+    assert_eq!(open_error(&db.path), "File is not a bizdb database.");
+    std::fs::write(&db.path, [7u8; 4096]).unwrap();
+    assert_eq!(open_error(&db.path), "File is not a bizdb database.");
+    /* Refused before a byte of it was touched */
+    assert_eq!(std::fs::read(&db.path).unwrap(), [7u8; 4096]);
+}
+
+#[test]
+fn other_versions_are_refused() {
+    let db = TempDb::new("version");
+    create_users(&db.path);
+    repl(&[], &db.path,
+         "insert into users 1 user1 person1@example.com\n.exit\n");
+    let mut file = std::fs::read(&db.path).unwrap();
+    assert_eq!(&file[0..16], b"bizdb database\x00\x00");
+    file[16..20].copy_from_slice(&99u32.to_le_bytes());
+    std::fs::write(&db.path, &file).unwrap();
+    assert_eq!(open_error(&db.path),
+               "Database format version 99 is newer than supported version 6.");
+    assert_eq!(std::fs::read(&db.path).unwrap(), file);
+    file[16..20].copy_from_slice(&5u32.to_le_bytes());
+    std::fs::write(&db.path, &file).unwrap();
+    assert_eq!(open_error(&db.path),
+               "Database format version 5 is older than supported version 6 and cannot be upgraded.");
+    assert_eq!(std::fs::read(&db.path).unwrap(), file);
+    for version in &[1u32, 5, 99] {
+        file[16..20].copy_from_slice(&version.to_le_bytes());
+        assert_refused(&db, &file, Error::Version(*version));
+    }
+    assert_eq!(Error::Version(0).to_string(),
+               "Database file predates the bizdb file header and cannot be upgraded.");
+}
+
+#[test]
+fn corrupt_pages_are_refused() {
+    let db = TempDb::new("checksum");
+    create_users(&db.path);
+    let mut input = String::new();
+    for id in 1..=100 {
+        input += &format!("{}\n", insert_user(id));
+    }
+    repl(&[], &db.path, &(input + ".exit\n"));
+    let mut file = std::fs::read(&db.path).unwrap();
+    /* Node type 1 is a leaf, page 1 holds the catalog */
+    let leaf = (2..file.len() / 4096).find(|page| file[page * 4096] == 1)
+        .unwrap();
+    file[leaf * 4096 + 100] ^= 0xff;
+    std::fs::write(&db.path, &file).unwrap();
+    /* The statement fails, the REPL goes on */
+    let output = repl(&[], &db.path, "select * from users\n.exit\n");
+    assert_eq!(output,
+               format!("db > Error: Database page {} is corrupt.\ndb > ", leaf));
+}
+
+#[test]
+fn page_size_is_kept_by_the_file() {
+    for page_size in &[512u32, 1024, 65536] {
+        let db = TempDb::new(&format!("page-size-{}", page_size));
+        {
+            let database =
+                Arc::new(Database::open_with_page_size(&db.path, *page_size)
+                             .unwrap());
+            let mut connection = Connection::new(&database);
+            connection.execute(CREATE_USERS).unwrap();
+            /* Enough for internal nodes at the smaller sizes */
+            connection.execute("begin").unwrap();
+            for id in 1..=1000 {
+                connection.execute(&insert_user(id)).unwrap();
+            }
+            connection.execute("commit").unwrap();
+        }
+        let file = std::fs::read(&db.path).unwrap();
+        assert_eq!(file[20..24], page_size.to_le_bytes());
+        assert_eq!(file.len() % *page_size as usize, 0);
+        /* Reopening with another size uses the one in the file */
+        {
+            let database = Database::open_with_page_size(&db.path, 4096)
+                .unwrap();
+            let ids: Vec<u32> =
+                database.scan("users").unwrap()
+                    .map(|row| row.unwrap().id().unwrap()).collect();
+            assert_eq!(ids, (1..=1000).collect::<Vec<u32>>());
+        }
+        assert_eq!(check(&db.path), "ok", "page size {}", page_size);
+    }
+    let db = TempDb::new("page-size-bad");
+    for page_size in &[256u32, 1000, 131072] {
+        assert_eq!(Database::open_with_page_size(&db.path, *page_size).err(),
+                   Some(Error::PageSize(*page_size)));
+    }
+    assert!(!std::path::Path::new(&db.path).exists());
+    assert_eq!(repl(&["--page-size=1000"], &db.path, ".exit\n").trim(),
+               Error::PageSize(1000).to_string());
+}
+
+#[test]
+fn files_without_a_header_are_refused() {
+    let db = TempDb::new("legacy");
+    /* The root on page 0 */
+    assert_refused(&db, &legacy_leaf(3), Error::Version(0));
+    /* A free-list header on page 0 and the root on page 1 */
+    let mut file = vec![0u8; 4096];
+    file[0..4].copy_from_slice(&2u32.to_le_bytes());
+    file[4..8].copy_from_slice(&1u32.to_le_bytes());
+    file.extend(legacy_leaf(0));
+    file.extend(vec![0u8; 4096]);
+    assert_refused(&db, &file, Error::Version(0));
+    assert_refused(&db, &[7u8; 4096], Error::NotADatabase);
+    assert_refused(&db, b"not a database", Error::NotADatabase);
+}
+
+#[test]
+fn record_values_are_little_endian() {
+    let db = TempDb::new("endian");
+    repl(&[], &db.path,
+         "create table numbers (id integer, n integer, r real)\n\
+          insert into numbers 1 72623859790382856 0.5\n.exit\n");
+    let file = std::fs::read(&db.path).unwrap();
+    let contains = |bytes: &[u8]| file.windows(8).any(|window| window == bytes);
+    assert!(contains(&0x0102030405060708i64.to_le_bytes()));
+    assert!(contains(&0.5f64.to_le_bytes()));
+}
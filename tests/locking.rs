@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+
+const LOCKED: &str = "Error: database is locked.";
+
+fn writer_excludes_writers(journal_mode: &str, name: &str) {
+    let db = TempDb::new(name);
+    create_users(&db.path);
+    let mode = format!("--journal-mode={}", journal_mode);
+    let mut a = Repl::open(&[&mode], &db.path);
+    let mut b = Repl::open(&[&mode], &db.path);
+    b.run("pragma busy_timeout = 50");
+    assert_eq!(a.run("insert into users 1 a a@example.com"), vec!["Executed."]);
+    assert_eq!(b.run("select * from users"),
+               vec!["(1, a, a@example.com)", "Executed."]);
+    a.run("begin");
+    a.run("insert into users 2 b b@example.com");
+    /* In WAL mode readers go on from the last commit */
+    if journal_mode == "wal" {
+        assert_eq!(b.run("select * from users"),
+                   vec!["(1, a, a@example.com)", "Executed."]);
+    } else {
+        assert_eq!(b.run("select * from users"), vec![LOCKED]);
+    }
+    assert_eq!(b.run("insert into users 3 c c@example.com"), vec![LOCKED]);
+    a.run("commit");
+    assert_eq!(b.run("insert into users 3 c c@example.com"), vec!["Executed."]);
+    assert_eq!(a.run("select * from users"),
+               vec!["(1, a, a@example.com)", "(2, b, b@example.com)",
+                    "(3, c, c@example.com)", "Executed."]);
+    a.exit();
+    b.exit();
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn writer_excludes_writers_wal() { writer_excludes_writers("wal", "lock-wal"); }
+
+#[test]
+fn writer_excludes_writers_rollback() {
+    writer_excludes_writers("rollback", "lock-rollback");
+}
+
+#[test]
+fn readers_keep_their_snapshot_during_a_checkpoint() {
+    let db = TempDb::new("snapshot");
+    create_users(&db.path);
+    let mut writer = Repl::open(&[], &db.path);
+    let mut reader = Repl::open(&[], &db.path);
+    /* The writer never waits for the reader */
+    writer.run("pragma busy_timeout = 0");
+    for id in 1..=10 {
+        writer.run(&insert_user(id));
+    }
+    reader.run("begin");
+    assert_eq!(reader.run("select * from users").len(), 11);
+    for id in 11..=200 {
+        assert_eq!(writer.run(&insert_user(id)),
+                   vec!["Executed."]);
+    }
+    assert_eq!(writer.run(".checkpoint"), Vec::<String>::new());
+    /* Frames past its snapshot were left in the WAL */
+    assert_eq!(reader.run("select * from users").len(), 11);
+    assert_eq!(reader.run(".check"), vec!["ok"]);
+    reader.run("commit");
+    assert_eq!(reader.run("select * from users").len(), 201);
+    /* With the reader gone the WAL starts over */
+    writer.run(".checkpoint");
+    assert!(writer.run(".stats").contains(&"WAL frames: 0".to_string()));
+    writer.exit();
+    reader.exit();
+    assert_eq!(check(&db.path), "ok");
+}
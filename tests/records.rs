@@ -0,0 +1,199 @@
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+
+fn execute(connection: &mut Connection, sql: &str)
+ -> Result<ExecuteResult, Error> {
+    connection.execute(sql).map(|executed| executed.result)
+}
+
+#[test]
+fn rows_follow_the_declared_columns() {
+    let db = TempDb::new("records");
+    {
+        let database = Arc::new(Database::open(&db.path).unwrap());
+        let mut connection = Connection::new(&database);
+        assert_eq!(execute(&mut connection, "insert into people 1 ada"),
+                   Err(Error::Syntax(PrepareResult::NoSuchTable)));
+        /* The key has to be an integer column and come first */
+        assert!(execute(&mut connection,
+                        "create table people (name text(10), id integer)")
+                    .is_err());
+        assert!(execute(&mut connection, "create table people (id real)")
+                    .is_err());
+        execute(&mut connection,
+                "create table people (id integer, name text(10), age integer)")
+            .unwrap();
+        assert_eq!(execute(&mut connection, "create table people (id integer)"),
+                   Err(Error::TableExists));
+        execute(&mut connection, "insert into people 2 ada 36").unwrap();
+        execute(&mut connection, "insert into people 1 grace 85").unwrap();
+        assert_eq!(execute(&mut connection, "insert into people 3 abcdefghijk 1"),
+                   Err(Error::Syntax(PrepareResult::StringTooLong)));
+        assert!(execute(&mut connection, "insert into people 3 bob").is_err());
+    }
+    /* The schema is read back from the file */
+    let output = repl(&[], &db.path, "select * from people\n.exit\n");
+    assert_eq!(output, "db > (1, grace, 85)\n(2, ada, 36)\nExecuted.\ndb > ");
+    let database = Database::open(&db.path).unwrap();
+    assert_eq!(database.get("people", 2).unwrap(),
+               Some(Row::new(vec![Value::Integer(2),
+                                  Value::Text("ada".to_string()),
+                                  Value::Integer(36)])));
+    database.close().unwrap();
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn tables_share_one_file() {
+    let db = TempDb::new("catalog");
+    {
+        let database = Arc::new(Database::open(&db.path).unwrap());
+        let mut connection = Connection::new(&database);
+        execute(&mut connection, CREATE_USERS).unwrap();
+        execute(&mut connection, "create table notes (id integer, body text(20))")
+            .unwrap();
+        for id in 1..=50 {
+            execute(&mut connection, &insert_user(id)).unwrap();
+            execute(&mut connection,
+                    &format!("insert into notes {} note{}", 100 - id, id))
+                .unwrap();
+        }
+        execute(&mut connection, "delete from notes where id between 1 and 90")
+            .unwrap();
+        /* The catalog can be read but only CREATE TABLE writes it */
+        assert_eq!(execute(&mut connection,
+                           "insert into bizdb_catalog 9 t 9 x"),
+                   Err(Error::Syntax(PrepareResult::ReadOnly)));
+        let rows: Vec<Row> =
+            connection.execute("select * from bizdb_catalog").unwrap().rows;
+        let names: Vec<Value> =
+            rows.iter().map(|row| row.values[1].clone()).collect();
+        assert_eq!(names, vec![Value::Text("users".to_string()),
+                               Value::Text("notes".to_string())]);
+    }
+    let tables = repl(&[], &db.path, ".tables\n.exit\n");
+    assert_eq!(tables, "db > users\nnotes\ndb > ");
+    let database = Database::open(&db.path).unwrap();
+    let ids = |table: &str| -> Vec<u32> {
+        database.scan(table).unwrap()
+            .map(|row| row.unwrap().id().unwrap()).collect()
+    };
+    assert_eq!(ids("users"), (1..=50).collect::<Vec<u32>>());
+    assert_eq!(ids("notes"), (91..=99).collect::<Vec<u32>>());
+    assert_eq!(database.get("notes", 95).unwrap().unwrap().values[1],
+               Value::Text("note5".to_string()));
+    assert_eq!(database.get("missing", 1),
+               Err(Error::Syntax(PrepareResult::NoSuchTable)));
+    database.close().unwrap();
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn typed_values_round_trip() {
+    let db = TempDb::new("typed");
+    {
+        let database = Arc::new(Database::open(&db.path).unwrap());
+        let mut connection = Connection::new(&database);
+        execute(&mut connection,
+                "create table people (id integer, name text(10), score real, photo blob(4), member boolean)")
+            .unwrap();
+        execute(&mut connection,
+                "insert into people 1 ada 2.5 x'00ff10' true").unwrap();
+        execute(&mut connection, "insert into people 2 null null null false")
+            .unwrap();
+        assert_eq!(execute(&mut connection,
+                           "insert into people 3 bob 1 null maybe"),
+                   Err(Error::Syntax(PrepareResult::TypeMismatch)));
+        assert_eq!(execute(&mut connection,
+                           "insert into people 3 bob 1 x'0' true"),
+                   Err(Error::Syntax(PrepareResult::TypeMismatch)));
+        assert_eq!(execute(&mut connection,
+                           "insert into people 3 bob 1 x'0011223344' true"),
+                   Err(Error::Syntax(PrepareResult::StringTooLong)));
+        /* Keys are not null and fit the 32 bit keys of the tree */
+        assert_eq!(execute(&mut connection,
+                           "insert into people null bob 1 null true"),
+                   Err(Error::Syntax(PrepareResult::NullId)));
+        assert_eq!(execute(&mut connection,
+                           "insert into people 5000000000 bob 1 null true"),
+                   Err(Error::Syntax(PrepareResult::IdTooLarge)));
+        database.insert("people",
+                        Row::new(vec![Value::Integer(3), Value::Null,
+                                      Value::Real(-0.125),
+                                      Value::Blob(vec![1, 2, 3, 4]),
+                                      Value::Null])).unwrap();
+        assert_eq!(database.insert("people",
+                                   Row::new(vec![Value::Integer(4),
+                                                 Value::Real(1.0), Value::Null,
+                                                 Value::Null, Value::Null])),
+                   Err(Error::Syntax(PrepareResult::TypeMismatch)));
+    }
+    let output = repl(&[], &db.path, "select * from people\n.exit\n");
+    assert_eq!(output,
+               "db > (1, ada, 2.5, x'00ff10', true)\n\
+                (2, null, null, null, false)\n\
+                (3, null, -0.125, x'01020304', null)\nExecuted.\ndb > ");
+    let database = Database::open(&db.path).unwrap();
+    assert_eq!(database.get("people", 1).unwrap().unwrap().values,
+               vec![Value::Integer(1), Value::Text("ada".to_string()),
+                    Value::Real(2.5), Value::Blob(vec![0, 255, 16]),
+                    Value::Boolean(true)]);
+    database.close().unwrap();
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn long_values_spill_onto_overflow_pages() {
+    let db = TempDb::new("overflow");
+    /* Spans many overflow pages */
+    let long_text = "abcdefgh".repeat(5000);
+    let long_blob: Vec<u8> = (0..100000).map(|i| (i % 251) as u8).collect();
+    {
+        let database = Arc::new(Database::open(&db.path).unwrap());
+        let mut connection = Connection::new(&database);
+        execute(&mut connection,
+                "create table notes (id integer, body text, photo blob)")
+            .unwrap();
+        execute(&mut connection,
+                &format!("insert into notes 1 {} null", long_text)).unwrap();
+        execute(&mut connection, "insert into notes 2 short x'0102'").unwrap();
+        database.insert("notes",
+                        Row::new(vec![Value::Integer(3), Value::Null,
+                                      Value::Blob(long_blob.clone())]))
+            .unwrap();
+    }
+    {
+        let database = Database::open(&db.path).unwrap();
+        assert_eq!(database.get("notes", 1).unwrap().unwrap().values,
+                   vec![Value::Integer(1), Value::Text(long_text),
+                        Value::Null]);
+        assert_eq!(database.get("notes", 2).unwrap().unwrap().values,
+                   vec![Value::Integer(2), Value::Text("short".to_string()),
+                        Value::Blob(vec![1, 2])]);
+        assert_eq!(database.get("notes", 3).unwrap().unwrap().values,
+                   vec![Value::Integer(3), Value::Null,
+                        Value::Blob(long_blob)]);
+        database.close().unwrap();
+    }
+    assert_eq!(check(&db.path), "ok");
+    /* Shrinking and deleting records frees their overflow pages */
+    {
+        let database = Arc::new(Database::open(&db.path).unwrap());
+        let mut connection = Connection::new(&database);
+        execute(&mut connection, "update notes set body = tiny where id = 1")
+            .unwrap();
+        execute(&mut connection, "delete from notes where id = 3").unwrap();
+        assert_eq!(database.get("notes", 1).unwrap().unwrap().values,
+                   vec![Value::Integer(1), Value::Text("tiny".to_string()),
+                        Value::Null]);
+    }
+    let freelist = repl(&[], &db.path, ".freelist\n.exit\n");
+    let free: u32 =
+        freelist.split("Free pages: ").nth(1).unwrap().split('\n').next()
+            .unwrap().parse().unwrap();
+    assert!(free > 30, "{}", freelist);
+    assert_eq!(check(&db.path), "ok");
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::*;
+
+fn insert_rows(repl: &mut Repl, ids: impl Iterator<Item = u32>) {
+    for id in ids {
+        assert_eq!(repl.run(&insert_user(id)),
+                   vec!["Executed."]);
+    }
+}
+
+#[test]
+fn wal_is_replayed_after_a_crash() {
+    let db = TempDb::new("wal-crash");
+    create_users(&db.path);
+    let wal = format!("{}-wal", db.path);
+    let mut writer = Repl::open(&[], &db.path);
+    insert_rows(&mut writer, 1..=100);
+    writer.kill();
+    /* The commits only reached the WAL */
+    assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+    /* A frame cut short by the crash is not replayed, the ones before are */
+    let length = std::fs::metadata(&wal).unwrap().len();
+    let file = std::fs::OpenOptions::new().write(true).open(&wal).unwrap();
+    file.set_len(length - 100).unwrap();
+    assert_eq!(select_ids(&db.path), (1..=99).collect::<Vec<u32>>());
+    /* A clean close checkpoints what was recovered and drops the WAL */
+    assert!(!std::path::Path::new(&wal).exists());
+    assert_eq!(select_ids(&db.path), (1..=99).collect::<Vec<u32>>());
+}
+
+#[test]
+fn rollback_journal_is_removed_on_commit() {
+    let db = TempDb::new("journal-commit");
+    create_users(&db.path);
+    let journal = format!("{}-journal", db.path);
+    let mut writer = Repl::open(&["--journal-mode=rollback"], &db.path);
+    assert_eq!(writer.run("pragma journal_mode"),
+               vec!["journal_mode = rollback", "Executed."]);
+    insert_rows(&mut writer, 1..=50);
+    /* Every statement commits straight into the database file */
+    assert!(!std::path::Path::new(&journal).exists());
+    assert!(!std::path::Path::new(&format!("{}-wal", db.path)).exists());
+    writer.kill();
+    /* A journal without a valid header covers nothing and is dropped */
+    std::fs::write(&journal, vec![0x5a; 1000]).unwrap();
+    assert_eq!(select_ids(&db.path), (1..=50).collect::<Vec<u32>>());
+    assert!(!std::path::Path::new(&journal).exists());
+}
+
+#[test]
+fn hot_journal_is_rolled_back() {
+    let db = TempDb::new("journal-crash");
+    create_users(&db.path);
+    let journal = format!("{}-journal", db.path);
+    let mut writer = Repl::open(&["--journal-mode=rollback"], &db.path);
+    insert_rows(&mut writer, 1..=100);
+    let committed = std::fs::read(&db.path).unwrap();
+    /* A cache of one page spills the changes into the database file */
+    writer.run(".cache_size 1");
+    writer.run("begin");
+    writer.run("delete from users where id between 1 and 50");
+    insert_rows(&mut writer, 101..=300);
+    writer.kill();
+    assert!(std::path::Path::new(&journal).exists());
+    assert_ne!(std::fs::read(&db.path).unwrap(), committed);
+    assert_eq!(select_ids(&db.path), (1..=100).collect::<Vec<u32>>());
+    assert!(!std::path::Path::new(&journal).exists());
+}
+
+fn transaction_rollback(journal_mode: &str, name: &str) {
+    let db = TempDb::new(name);
+    create_users(&db.path);
+    let mut session =
+        Repl::open(&[&format!("--journal-mode={}", journal_mode)], &db.path);
+    insert_rows(&mut session, 1..=3);
+    assert_eq!(session.run("begin"), vec!["Executed."]);
+    assert_eq!(session.run("begin"),
+               vec!["Error: Not allowed inside a transaction."]);
+    insert_rows(&mut session, 4..=100);
+    session.run("delete from users where id < 3");
+    assert_eq!(session.run("rollback"), vec!["Executed."]);
+    assert_eq!(session.run("commit"), vec!["Error: No transaction is active."]);
+    session.run("begin");
+    insert_rows(&mut session, 4..=5);
+    session.run("commit");
+    session.exit();
+    assert_eq!(select_ids(&db.path), (1..=5).collect::<Vec<u32>>());
+}
+
+#[test]
+fn transaction_rollback_wal() { transaction_rollback("wal", "rollback-wal"); }
+
+#[test]
+fn transaction_rollback_rollback() {
+    transaction_rollback("rollback", "rollback-rollback");
+}
+
+fn savepoint_rollback(journal_mode: &str, name: &str) {
+    let db = TempDb::new(name);
+    create_users(&db.path);
+    let mut session =
+        Repl::open(&[&format!("--journal-mode={}", journal_mode)], &db.path);
+    session.run("begin");
+    insert_rows(&mut session, 1..=3);
+    /* The root leaf splits and new internal levels appear */
+    assert_eq!(session.run("savepoint a"), vec!["Executed."]);
+    insert_rows(&mut session, 4..=400);
+    session.run("savepoint b");
+    session.run("delete from users where id between 1 and 300");
+    assert_eq!(session.run("rollback to b"), vec!["Executed."]);
+    session.run("rollback to a");
+    insert_rows(&mut session, 4..=4);
+    assert_eq!(session.run("release a"), vec!["Executed."]);
+    session.run("commit");
+    session.exit();
+    assert_eq!(select_ids(&db.path), vec![1, 2, 3, 4]);
+    let tree = repl(&[], &db.path, ".btree\n.exit\n");
+    assert!(tree.contains("Tree of users:\n- leaf (size 4)\n"), "{}", tree);
+}
+
+#[test]
+fn savepoint_rollback_across_splits_wal() {
+    savepoint_rollback("wal", "savepoint-wal");
+}
+
+#[test]
+fn savepoint_rollback_across_splits_rollback() {
+    savepoint_rollback("rollback", "savepoint-rollback");
+}
+
+#[test]
+fn wal_cut_short_under_a_connection_is_corrupt() {
+    let db = TempDb::new("wal-cut");
+    create_users(&db.path);
+    let wal = format!("{}-wal", db.path);
+    let mut writer = Repl::open(&[], &db.path);
+    insert_rows(&mut writer, 1..=100);
+    writer.exit();
+    let mut writer = Repl::open(&[], &db.path);
+    writer.run(".cache_size 1");
+    writer.run("begin");
+    insert_rows(&mut writer, 101..=200);
+    /* The leaves spilled to the WAL vanish from under the connection */
+    let file = std::fs::OpenOptions::new().write(true).open(&wal).unwrap();
+    file.set_len(32).unwrap();
+    let output = writer.run("select * from users");
+    /* Reported by the read of a leaf, not by the header or the catalog */
+    assert_eq!(output, vec!["Error: Database page 14 is corrupt."]);
+    writer.kill();
+}
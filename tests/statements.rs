@@ -0,0 +1,62 @@
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+
+#[test]
+fn update_changes_only_the_named_columns() {
+    let db = TempDb::new("update");
+    create_users(&db.path);
+    let output = repl(&[], &db.path,
+                      "insert into users 1 alice alice@example.com\n\
+                       insert into users 2 bob bob@example.com\n\
+                       update users set username = carol where id = 1\n\
+                       update users set email = robert@example.com, \
+                       username = rob where id = 2\n\
+                       update users set username = nobody where id = 3\n\
+                       update users set nickname = x where id = 1\n\
+                       select * from users\n.exit\n");
+    assert!(output.contains("0 rows affected."), "{}", output);
+    assert!(output.contains("Syntax error"), "{}", output);
+    assert!(output.contains("(1, carol, alice@example.com)\n\
+                             (2, rob, robert@example.com)\n"), "{}", output);
+}
+
+#[test]
+fn where_keys_are_checked_like_inserted_keys() {
+    let db = TempDb::new("where");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut connection = Connection::new(&database);
+    connection.execute(CREATE_USERS).unwrap();
+    for id in &[1u32, 2, 4294967295] {
+        connection.execute(&format!("insert into users {} a a@example.com",
+                                    id)).unwrap();
+    }
+    let mut refused = |sql: &str, error: PrepareResult| {
+        assert_eq!(connection.execute(sql),
+                   Err(Error::Syntax(error)), "{}", sql);
+    };
+    refused("update users set username = b where id = 1abc",
+            PrepareResult::TypeMismatch);
+    refused("update users set username = b where id = 4294967297",
+            PrepareResult::IdTooLarge);
+    refused("update users set username = b where id = -1",
+            PrepareResult::NegativeId);
+    refused("update users set username = b where id = null",
+            PrepareResult::NullId);
+    refused("delete from users where id = 2x",
+            PrepareResult::TypeMismatch);
+    refused("delete from users where id between 1 and 4294967297",
+            PrepareResult::IdTooLarge);
+    /* Nothing lies above the largest key */
+    connection.execute("delete from users where id > 4294967295").unwrap();
+    connection.execute("update users set username = b where id = 4294967295")
+        .unwrap();
+    let ids: Vec<u32> =
+        database.scan("users").unwrap()
+            .map(|row| row.unwrap().id().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 4294967295]);
+    assert_eq!(database.get("users", 4294967295).unwrap().unwrap().values[1],
+               Value::Text("b".to_string()));
+}
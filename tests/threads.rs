@@ -0,0 +1,88 @@
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+use std::thread;
+
+fn shareable<T: Send + Sync>() { }
+
+fn batch_insert(connection: &mut Connection, worker: u32, batch: u32)
+ -> Result<(), Error> {
+    connection.execute("begin")?;
+    for i in 0..10u32 {
+        let id = worker * 1000 + batch * 10 + i + 1;
+        let sql = format!("insert into users {} user{} user{}@example.com",
+                          id, id, id);
+        assert_eq!(connection.execute(&sql)?.result,
+                   ExecuteResult::Success);
+    }
+    connection.execute("commit")?;
+    Ok(())
+}
+
+#[test]
+fn database_is_shared_between_threads() {
+    shareable::<Database>();
+    let db = TempDb::new("threads");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    Connection::new(&database).execute(CREATE_USERS).unwrap();
+    let mut workers = Vec::new();
+    for worker in 0..8u32 {
+        let database = database.clone();
+        workers.push(thread::spawn(move || {
+            let mut connection = Connection::new(&database);
+            for batch in 0..5u32 {
+                /*
+  A transaction whose snapshot another one committed over, or
+  that waited out the busy timeout, is rolled back and retried
+  */
+                while let Err(error) = batch_insert(&mut connection, worker,
+                                                    batch) {
+                    assert_eq!(error, Error::Locked);
+                    connection.execute("rollback").unwrap();
+                }
+                /* The handle itself takes single statements */
+                let id = worker * 1000 + 500 + batch;
+                let email = Value::Text("y@example.com".to_string());
+                let row = Row::new(vec![Value::Integer(id.into()),
+                                        Value::Text("y".to_string()), email]);
+                database.insert("users", row).unwrap();
+                assert!(database.get("users", id).unwrap().is_some());
+                let ids: Vec<u32> =
+                    database.scan("users").unwrap()
+                        .map(|row| row.unwrap().id().unwrap()).collect();
+                assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }));
+    }
+    for worker in workers { worker.join().unwrap(); }
+    assert_eq!(database.scan("users").unwrap().count(), 8 * 55);
+    Arc::try_unwrap(database).ok().unwrap().close().unwrap();
+    assert_eq!(check(&db.path), "ok");
+}
+
+#[test]
+fn transactions_do_not_hold_up_other_calls() {
+    let db = TempDb::new("threads-begin");
+    let database = Arc::new(Database::open(&db.path).unwrap());
+    let mut writer = Connection::new(&database);
+    writer.execute(CREATE_USERS).unwrap();
+    writer.execute(&insert_user(1)).unwrap();
+    writer.execute("begin").unwrap();
+    writer.execute(&insert_user(2)).unwrap();
+    /* The same thread still reads what was committed */
+    assert!(database.get("users", 1).unwrap().is_some());
+    assert_eq!(database.get("users", 2).unwrap(), None);
+    assert_eq!(database.scan("users").unwrap().count(), 1);
+    /* A second writer gives up once its busy timeout has passed */
+    let mut other = Connection::new(&database);
+    other.execute("pragma busy_timeout = 50").unwrap();
+    assert_eq!(other.execute(&insert_user(3)), Err(Error::Locked));
+    writer.execute("commit").unwrap();
+    other.execute(&insert_user(3)).unwrap();
+    let ids: Vec<u32> =
+        database.scan("users").unwrap()
+            .map(|row| row.unwrap().id().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+}
@@ -0,0 +1,106 @@
+/*
+  Crash recovery: cut the Nth write of a transaction and its
+  checkpoint in half for every N, reopen, and expect the last
+  committed state. Needs the torn-write feature, which the
+  dev-dependency on bizdb itself turns on.
+  */
+mod common;
+
+use bizdb::*;
+use common::*;
+use std::sync::Arc;
+
+fn ids(database: &Database) -> Vec<u32> {
+    database.scan("users").unwrap()
+        .map(|row| row.unwrap().id().unwrap()).collect()
+}
+
+fn transaction(connection: &mut Connection) -> Result<(), Error> {
+    /* Enough rows to split and merge leaves */
+    connection.execute("begin")?;
+    for id in 41..=80 {
+        connection.execute(&insert_user(id))?;
+    }
+    connection.execute("delete from users where id between 1 and 20")?;
+    connection.execute("commit")?;
+    Ok(())
+}
+
+fn torn_write(journal_mode: JournalMode, synchronous: Synchronous,
+              name: &str) {
+    let before: Vec<u32> = (1..=40).collect();
+    let after: Vec<u32> = (21..=80).collect();
+    let mut countdown = 1;
+    loop {
+        let db = TempDb::new(&format!("torn-{}-{}", name, countdown));
+        let mut committed = false;
+        let mut finished = false;
+        {
+            let database = Arc::new(Database::open(&db.path).unwrap());
+            database.set_journal_mode(journal_mode).unwrap();
+            database.set_synchronous(synchronous);
+            let mut connection = Connection::new(&database);
+            connection.execute(CREATE_USERS).unwrap();
+            for id in 1..=40 {
+                connection.execute(&insert_user(id)).unwrap();
+            }
+            connection.meta_command(&format!(".torn_write {}",
+                                             countdown)).unwrap();
+            if transaction(&mut connection).is_ok() {
+                committed = true;
+                finished = connection.meta_command(".checkpoint").is_ok();
+            }
+            /* Closing a crashed database leaves its files alone */
+        }
+        let database = Database::open(&db.path).unwrap();
+        let recovered = ids(&database);
+        if committed {
+            assert_eq!(recovered, after, "{} write {}", name, countdown);
+        } else {
+            assert!(recovered == before || recovered == after,
+                    "{} write {}: {:?}", name, countdown, recovered);
+        }
+        drop(database);
+        assert_eq!(check(&db.path), "ok", "{} write {}", name, countdown);
+        if finished { break }
+        countdown += 1;
+    }
+    /* Every write of the transaction and the checkpoint got torn once */
+    assert!(countdown > 10, "{} only took {} writes", name, countdown);
+}
+
+#[test]
+fn wal_off() {
+    torn_write(JournalMode::Wal, Synchronous::Off,
+               "wal-off");
+}
+
+#[test]
+fn wal_normal() {
+    torn_write(JournalMode::Wal,
+               Synchronous::Normal, "wal-normal");
+}
+
+#[test]
+fn wal_full() {
+    torn_write(JournalMode::Wal, Synchronous::Full,
+               "wal-full");
+}
+
+#[test]
+fn rollback_off() {
+    torn_write(JournalMode::Rollback,
+               Synchronous::Off, "rollback-off");
+}
+
+#[test]
+fn rollback_normal() {
+    torn_write(JournalMode::Rollback,
+               Synchronous::Normal, "rollback-normal");
+}
+
+#[test]
+fn rollback_full() {
+    torn_write(JournalMode::Rollback,
+               Synchronous::Full, "rollback-full");
+}